@@ -19,6 +19,8 @@ bitflags! {
 pub const EPOCH_1904: i32 = -1;
 pub const EPOCH_1900: i32 = 1;
 pub const EPOCH_1317: i32 = 6;
+/// Umm al-Qura dates, numbered after their Windows calendar code.
+pub const EPOCH_UMALQURA: i32 = 23;
 
 /// Excel date boundaries.
 pub const MIN_S_DATE: f64 = 0.0;
@@ -28,6 +30,9 @@ pub const MAX_S_DATE: f64 = 2_958_466.0;
 pub const MIN_L_DATE: f64 = -694_324.0;
 pub const MAX_L_DATE: f64 = 35_830_291.0;
 
+/// Largest integer that an `f64` represents exactly (`Number.MAX_SAFE_INTEGER`).
+pub const MAX_SAFE_INTEGER: i64 = 9_007_199_254_740_991;
+
 /// Known currency symbols supported by the JavaScript implementation.
 pub const CURRENCY_SYMBOLS: &[&str] = &[
     "¤", "$", "£", "¥", "֏", "؋", "৳", "฿", "៛", "₡", "₦", "₩", "₪", "₫", "€", "₭", "₮", "₱", "₲",
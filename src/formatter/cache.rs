@@ -0,0 +1,199 @@
+//! The process-wide cache of parsed patterns behind `format` and friends.
+//!
+//! Entries are evicted least recently used first once the cache holds
+//! `capacity` patterns, so ad-hoc codes cannot grow it without bound.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::parser::model::Pattern;
+use crate::parser::parse_pattern;
+
+use super::{FormatterError, build_error_pattern, options::FormatterOptions};
+
+/// How many patterns the cache keeps unless told otherwise.
+pub const DEFAULT_PATTERN_CACHE_CAPACITY: usize = 1024;
+
+/// Counters describing the pattern cache since it was last cleared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PatternCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub size: usize,
+    pub capacity: usize,
+}
+
+enum CachedPattern {
+    Valid(Arc<Pattern>),
+    Invalid {
+        message: String,
+        fallback: Arc<Pattern>,
+    },
+}
+
+struct Slot {
+    value: CachedPattern,
+    last_used: u64,
+}
+
+struct PatternCache {
+    entries: HashMap<String, Slot>,
+    /// Patterns by the tick they were last used at, oldest first.
+    recency: BTreeMap<u64, String>,
+    tick: u64,
+    capacity: usize,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+static PATTERN_CACHE: OnceLock<Mutex<PatternCache>> = OnceLock::new();
+
+fn pattern_cache() -> &'static Mutex<PatternCache> {
+    PATTERN_CACHE.get_or_init(|| Mutex::new(PatternCache::new(DEFAULT_PATTERN_CACHE_CAPACITY)))
+}
+
+impl PatternCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            tick: 0,
+            capacity,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+        }
+    }
+
+    fn get(&mut self, pattern: &str) -> Option<&CachedPattern> {
+        self.tick += 1;
+        let Some(slot) = self.entries.get_mut(pattern) else {
+            self.misses += 1;
+            return None;
+        };
+        self.hits += 1;
+        let key = self
+            .recency
+            .remove(&slot.last_used)
+            .unwrap_or_else(|| pattern.to_string());
+        slot.last_used = self.tick;
+        self.recency.insert(self.tick, key);
+        Some(&slot.value)
+    }
+
+    fn insert(&mut self, pattern: &str, value: CachedPattern) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.tick += 1;
+        if let Some(old) = self.entries.insert(
+            pattern.to_string(),
+            Slot {
+                value,
+                last_used: self.tick,
+            },
+        ) {
+            self.recency.remove(&old.last_used);
+        }
+        self.recency.insert(self.tick, pattern.to_string());
+        self.shrink();
+    }
+
+    fn shrink(&mut self) {
+        while self.entries.len() > self.capacity {
+            let Some((_, oldest)) = self.recency.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+            self.evictions += 1;
+        }
+    }
+
+    fn stats(&self) -> PatternCacheStats {
+        PatternCacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            evictions: self.evictions,
+            size: self.entries.len(),
+            capacity: self.capacity,
+        }
+    }
+}
+
+/// Drops every cached pattern and resets the counters.
+pub fn clear_pattern_cache() {
+    let mut cache = pattern_cache().lock().expect("pattern cache poisoned");
+    let capacity = cache.capacity;
+    *cache = PatternCache::new(capacity);
+}
+
+/// Sets how many patterns the cache keeps, evicting the least recently
+/// used ones beyond it. A capacity of 0 turns caching off.
+pub fn set_pattern_cache_capacity(capacity: usize) {
+    let mut cache = pattern_cache().lock().expect("pattern cache poisoned");
+    cache.capacity = capacity;
+    cache.shrink();
+}
+
+pub fn pattern_cache_stats() -> PatternCacheStats {
+    pattern_cache()
+        .lock()
+        .expect("pattern cache poisoned")
+        .stats()
+}
+
+/// Looks `pattern` up in the cache, parsing and storing it on a miss.
+/// With `options.cache_patterns` off the cache is not touched at all.
+pub(super) fn prepare_pattern(
+    pattern: &str,
+    options: &FormatterOptions,
+) -> Result<Arc<Pattern>, FormatterError> {
+    let should_throw = options.throws;
+    if !options.cache_patterns {
+        return match parse_pattern(pattern) {
+            Ok(parsed) => Ok(Arc::new(parsed)),
+            Err(err) if should_throw => Err(FormatterError::Parse(err)),
+            Err(err) => Ok(build_error_pattern(pattern, &err.to_string())),
+        };
+    }
+
+    let mut cache = pattern_cache().lock().expect("pattern cache poisoned");
+    if let Some(entry) = cache.get(pattern) {
+        return match entry {
+            CachedPattern::Valid(pat) => Ok(pat.clone()),
+            CachedPattern::Invalid { message, fallback } => {
+                if should_throw {
+                    Err(FormatterError::InvalidPattern(message.clone()))
+                } else {
+                    Ok(fallback.clone())
+                }
+            }
+        };
+    }
+
+    match parse_pattern(pattern) {
+        Ok(parsed) => {
+            let arc = Arc::new(parsed);
+            cache.insert(pattern, CachedPattern::Valid(arc.clone()));
+            Ok(arc)
+        }
+        Err(err) => {
+            let message = err.to_string();
+            let fallback = build_error_pattern(pattern, &message);
+            cache.insert(
+                pattern,
+                CachedPattern::Invalid {
+                    message,
+                    fallback: fallback.clone(),
+                },
+            );
+            if should_throw {
+                Err(FormatterError::Parse(err))
+            } else {
+                Ok(fallback)
+            }
+        }
+    }
+}
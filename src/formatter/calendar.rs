@@ -0,0 +1,560 @@
+//! The calendars a section can ask for besides the Gregorian one: the
+//! built-in Hijri, Umm al-Qura and Hebrew calendars and the registry of
+//! calendars added at runtime. Era-based calendars live in `era`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use thiserror::Error;
+
+use crate::constants::{EPOCH_1317, EPOCH_UMALQURA};
+use crate::parser::model::{CalendarKind, Section};
+
+use super::era::{Era, EraTable};
+use super::options::FormatterOptions;
+
+/// 1970-01-01 in serials counting the phantom 1900-02-29.
+const UNIX_EPOCH_LOTUS: i64 = 25_569;
+
+/// 1 Tishri AM 1 as days since 1970-01-01.
+const HEBREW_EPOCH: i64 = -2_092_590;
+
+/// 1 Muharram AH 1 of the tabular calendar (622-07-15 Julian) as days
+/// since 1970-01-01.
+const HIJRI_EPOCH: i64 = -492_149;
+const HIJRI_CYCLE_DAYS: i64 = 10_631;
+/// Years of the 30-year cycle that end with a 30-day Dhu al-Hijjah, in the
+/// variant Excel uses.
+const HIJRI_LEAP_YEARS: [i64; 11] = [2, 5, 7, 10, 13, 15, 18, 21, 24, 26, 29];
+
+/// The first year of `UMALQURA_MONTHS`, which starts on 1900-04-30.
+const UMALQURA_FIRST_YEAR: i32 = 1318;
+const UMALQURA_START: i64 = -25_448;
+/// Umm al-Qura month lengths from AH 1318 to 1500, one entry per year with
+/// bit `n` set when month `n + 1` has 30 days.
+const UMALQURA_MONTHS: [u16; 183] = [
+    0x56D, 0xB5A, 0x752, 0xF25, 0xE8A, 0xD16, 0xA56, 0xAB5, 0x6B4, 0xDA9, 0xB92, 0xB25, 0x64B,
+    0xA9B, 0x35A, 0x6D9, 0x5D4, 0xDA5, 0xD4A, 0xA95, 0x536, 0x975, 0x2F4, 0x6E9, 0x6D4, 0x6A9,
+    0x535, 0x25D, 0x4BD, 0x9BA, 0x3B4, 0xB69, 0xB2A, 0xA55, 0x4AD, 0xA5D, 0x2DA, 0x6D9, 0xEAA,
+    0xE94, 0xD2A, 0xC56, 0x4AE, 0xA6D, 0x56A, 0xD55, 0xD4A, 0xA93, 0x52B, 0xA5B, 0x53A, 0x6B5,
+    0xEA9, 0xD52, 0xD29, 0xA55, 0x4AD, 0x56D, 0xAEA, 0x6E4, 0xED1, 0xDA2, 0xAAA, 0x95A, 0x2DA,
+    0x5B9, 0xBB2, 0x764, 0x6C9, 0x555, 0x2AB, 0x4DB, 0xABA, 0x5B4, 0xDA9, 0xD52, 0xAA5, 0x92D,
+    0x26D, 0x8ED, 0x2DA, 0xAD5, 0xAA5, 0xA4B, 0x497, 0x937, 0x2B6, 0x975, 0xD69, 0xD52, 0xC95,
+    0x92B, 0x25B, 0x4DB, 0x9D5, 0x5D2, 0xDA5, 0xD4A, 0xA95, 0x54D, 0xAAD, 0x3AA, 0xBD2, 0xBC4,
+    0xB89, 0xA95, 0x52D, 0x5AD, 0xB6A, 0x6D4, 0xDC9, 0xD92, 0xAA6, 0x956, 0x2AE, 0x56D, 0x36A,
+    0xB55, 0xAAA, 0x94D, 0x49D, 0x95D, 0x2BA, 0x5B5, 0x5AA, 0xD55, 0xA9A, 0x92E, 0x26E, 0x55D,
+    0xADA, 0x6D4, 0x6A5, 0xB27, 0xA4D, 0x4AD, 0x56D, 0xB5A, 0x754, 0xF49, 0xE92, 0xD26, 0xA56,
+    0x356, 0x6B5, 0xBAA, 0xB92, 0xB25, 0x68B, 0xA9B, 0x55A, 0xADA, 0x5B4, 0xDA9, 0xB52, 0xA9A,
+    0x536, 0x276, 0x575, 0xAF2, 0x6D4, 0x6A9, 0x555, 0x2AD, 0x4BD, 0x9BA, 0x574, 0xB69, 0xB52,
+    0xA95, 0x52D, 0xA5D, 0x4DA, 0xAD9, 0x6B2, 0xE95, 0xE2A, 0xC96, 0x92E, 0xAAD, 0x56A, 0xD65,
+    0xD4A,
+];
+
+/// A calendar that shows dates in place of the Gregorian one, such as a
+/// 4-4-5 retail calendar. Register it under a calendar code with
+/// [`add_calendar`] and pick it with that code in a locale tag
+/// (`[$-400409]`, `[$-en-US,40]`) or through `FormatterOptions::calendar`,
+/// or hand it to `FormatterOptions::custom_calendar` directly.
+///
+/// Date tokens take their fields from the calendar: `yyyy`, `yy` and `e`
+/// the year, `m` the month, `d` the day and `ddd` the weekday. Names left
+/// at `None` come from the locale.
+pub trait Calendar: Send + Sync {
+    /// The day `serial` falls on, or `None` for days the calendar has no
+    /// date for, which render as the invalid string. Serials count days
+    /// in the 1900 date system, where Lotus' phantom 1900-02-29 is 60;
+    /// 1904 serials arrive shifted to it.
+    fn day(&self, serial: i64) -> Option<CalendarDay>;
+
+    /// The name of `month` in `year`, written by `mmmm`, or by `mmm` when
+    /// `short` is set.
+    fn month_name(&self, _year: i32, _month: u8, _short: bool) -> Option<String> {
+        None
+    }
+
+    /// The name of a weekday, 0 for Sunday, written by `dddd`, or by `ddd`
+    /// when `short` is set.
+    fn weekday_name(&self, _weekday: u8, _short: bool) -> Option<String> {
+        None
+    }
+
+    /// The era of `day`, written by `g` to `ggg` as `width` 1 to 3.
+    fn era_name(&self, _day: &CalendarDay, _width: usize) -> Option<String> {
+        None
+    }
+
+    /// A year, month or day number written the calendar's own way, such
+    /// as in Hebrew letters. `None` writes it in digits.
+    fn numeral(&self, _number: i32) -> Option<String> {
+        None
+    }
+}
+
+impl fmt::Debug for dyn Calendar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Calendar")
+    }
+}
+
+/// Calendars compare by identity.
+impl PartialEq for dyn Calendar {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::addr_eq(self, other)
+    }
+}
+
+/// A day as a [`Calendar`] counts it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CalendarDay {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+    /// The day of the week, 0 for Sunday.
+    pub weekday: u8,
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum CalendarError {
+    #[error("calendar code {0:#x} belongs to a built-in calendar")]
+    BuiltIn(u8),
+}
+
+static CALENDARS: OnceLock<Mutex<HashMap<u8, Arc<dyn Calendar>>>> = OnceLock::new();
+
+fn calendars() -> &'static Mutex<HashMap<u8, Arc<dyn Calendar>>> {
+    CALENDARS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Registers `calendar` under a calendar code, replacing the calendar the
+/// code had. Codes of built-in calendars and the Gregorian variants
+/// cannot be taken.
+pub fn add_calendar(code: u8, calendar: impl Calendar + 'static) -> Result<(), CalendarError> {
+    if !matches!(CalendarKind::from_code(code), CalendarKind::Custom(_)) {
+        return Err(CalendarError::BuiltIn(code));
+    }
+    calendars()
+        .lock()
+        .expect("calendar registry poisoned")
+        .insert(code, Arc::new(calendar));
+    Ok(())
+}
+
+pub fn remove_calendar(code: u8) -> bool {
+    calendars()
+        .lock()
+        .expect("calendar registry poisoned")
+        .remove(&code)
+        .is_some()
+}
+
+/// The calendar a section shows dates in, when it is not Gregorian or
+/// counted in eras: a built-in one, the registered calendar of a custom
+/// code or `options.custom_calendar`. Unregistered codes show Gregorian
+/// dates.
+pub(super) fn resolve_calendar(
+    part: &Section,
+    options: &FormatterOptions,
+) -> Option<Arc<dyn Calendar>> {
+    match section_calendar(part, options) {
+        CalendarKind::Custom(code) => calendars()
+            .lock()
+            .expect("calendar registry poisoned")
+            .get(&code)
+            .cloned(),
+        CalendarKind::Gregorian if options.custom_calendar.is_some() => {
+            options.custom_calendar.clone()
+        }
+        calendar => builtin_calendar(calendar),
+    }
+}
+
+/// The built-in calendar behind a calendar kind, if it has one.
+pub(super) fn builtin_calendar(calendar: CalendarKind) -> Option<Arc<dyn Calendar>> {
+    match calendar {
+        CalendarKind::Hijri => Some(Arc::new(Hijri)),
+        CalendarKind::UmAlQura => Some(Arc::new(UmAlQura)),
+        CalendarKind::Hebrew => Some(Arc::new(Hebrew)),
+        _ => None,
+    }
+}
+
+/// The built-in calendar whose dates the fields of `system` hold.
+pub(super) fn system_calendar(system: i32) -> Option<Arc<dyn Calendar>> {
+    match system {
+        EPOCH_1317 => builtin_calendar(CalendarKind::Hijri),
+        EPOCH_UMALQURA => builtin_calendar(CalendarKind::UmAlQura),
+        _ => None,
+    }
+}
+
+/// The day of the week of a 1900 serial, 0 for Sunday. Like Excel this
+/// counts the phantom 1900-02-29, so 1900-01-01 is a Sunday.
+fn serial_weekday(serial: i64) -> u8 {
+    (serial + 6).rem_euclid(7) as u8
+}
+
+/// The tabular Hijri calendar, with Excel's quirks for its first serials.
+struct Hijri;
+
+impl Calendar for Hijri {
+    fn day(&self, serial: i64) -> Option<CalendarDay> {
+        // Excel shows its first serials as if the Hijri months around them
+        // had 30 days each and has no Hijri date for the phantom 1900-02-29.
+        let (year, month, day) = match serial {
+            60 => return None,
+            ..=1 => (1317, 8, 29),
+            2..60 => (
+                1317,
+                if serial < 32 { 9 } else { 10 },
+                1 + (serial - 2) % 30,
+            ),
+            _ => {
+                let (year, month, day) = hijri_from_days(serial - UNIX_EPOCH_LOTUS);
+                (year, month, i64::from(day))
+            }
+        };
+        Some(CalendarDay {
+            year,
+            month,
+            day: day as u8,
+            weekday: serial_weekday(serial),
+        })
+    }
+}
+
+/// The Umm al-Qura calendar over the years its table covers.
+struct UmAlQura;
+
+impl Calendar for UmAlQura {
+    fn day(&self, serial: i64) -> Option<CalendarDay> {
+        if serial <= 60 {
+            return None;
+        }
+        let (year, month, day) = umalqura_from_days(serial - UNIX_EPOCH_LOTUS)?;
+        Some(CalendarDay {
+            year,
+            month,
+            day,
+            weekday: serial_weekday(serial),
+        })
+    }
+}
+
+/// The Hebrew calendar, written in Hebrew letters.
+struct Hebrew;
+
+impl Calendar for Hebrew {
+    fn day(&self, serial: i64) -> Option<CalendarDay> {
+        // The phantom 1900-02-29 shows as the day after it.
+        let days = serial - UNIX_EPOCH_LOTUS + i64::from(serial < 61);
+        let (year, month, day) = hebrew_from_days(days);
+        Some(CalendarDay {
+            year,
+            month,
+            day,
+            weekday: serial_weekday(serial),
+        })
+    }
+
+    fn month_name(&self, year: i32, month: u8, _short: bool) -> Option<String> {
+        Some(hebrew_month_name(year, month).to_string())
+    }
+
+    fn numeral(&self, number: i32) -> Option<String> {
+        Some(hebrew_numeral(number))
+    }
+}
+
+/// A day as the calendar of a section shows it.
+pub(super) struct CalendarDate<'a> {
+    pub calendar: CalendarKind,
+    /// The year in the calendar, counted from the start of its era for
+    /// calendars that count in eras.
+    pub year: i32,
+    pub month: u8,
+    pub day: i32,
+    pub gregorian_year: i32,
+    /// The era of the day and the year within it, when eras apply.
+    pub era: Option<(&'a Era, i32)>,
+    /// The registered calendar showing the day, for the names it gives.
+    pub custom: Option<(Arc<dyn Calendar>, CalendarDay)>,
+}
+
+impl<'a> CalendarDate<'a> {
+    /// Takes the Gregorian fields a serial shows, counting the year in
+    /// the era for calendars that count in eras.
+    pub fn new(
+        calendar: CalendarKind,
+        eras: Option<&'a EraTable>,
+        year: i32,
+        month: u8,
+        day: i32,
+    ) -> Self {
+        let era = eras.and_then(|table| table.era_for(year, month, day as u8));
+        let calendar_year = match era {
+            Some((_, era_year)) if calendar.uses_eras() => era_year,
+            _ => year,
+        };
+        Self {
+            calendar,
+            year: calendar_year,
+            month,
+            day,
+            gregorian_year: year,
+            era,
+            custom: None,
+        }
+    }
+
+    /// Shows the day the way a non-Gregorian calendar counts it.
+    pub fn in_custom(self, calendar: Arc<dyn Calendar>, day: CalendarDay) -> Self {
+        Self {
+            year: day.year,
+            month: day.month,
+            day: day.day.into(),
+            era: None,
+            custom: Some((calendar, day)),
+            ..self
+        }
+    }
+}
+
+/// The calendar a section shows dates in. `options.calendar` applies to
+/// sections whose code leaves them Gregorian, unless
+/// `options.custom_calendar` takes them.
+pub(super) fn section_calendar(part: &Section, options: &FormatterOptions) -> CalendarKind {
+    match options.calendar {
+        Some(calendar)
+            if part.calendar == CalendarKind::Gregorian && options.custom_calendar.is_none() =>
+        {
+            calendar
+        }
+        _ => part.calendar,
+    }
+}
+
+/// A Hebrew date for days since 1970-01-01, with months counted from
+/// Tishri as Windows does: 13 of them in leap years, where Adar I is 6 and
+/// Adar II 7.
+fn hebrew_from_days(days: i64) -> (i32, u8, u8) {
+    let mut hebrew_year = (((days - HEBREW_EPOCH) * 98_496) / 35_975_351) as i32 + 1;
+    while hebrew_new_year(hebrew_year + 1) <= days {
+        hebrew_year += 1;
+    }
+    while hebrew_new_year(hebrew_year) > days {
+        hebrew_year -= 1;
+    }
+    let mut remaining = days - hebrew_new_year(hebrew_year);
+    for (idx, length) in hebrew_month_lengths(hebrew_year).into_iter().enumerate() {
+        if remaining < i64::from(length) {
+            return (hebrew_year, idx as u8 + 1, remaining as u8 + 1);
+        }
+        remaining -= i64::from(length);
+    }
+    unreachable!("a Hebrew year holds all of its days")
+}
+
+fn is_hebrew_leap_year(year: i32) -> bool {
+    (7 * i64::from(year) + 1).rem_euclid(19) < 7
+}
+
+/// Month lengths from Tishri to Elul.
+fn hebrew_month_lengths(year: i32) -> Vec<u8> {
+    let length = hebrew_new_year(year + 1) - hebrew_new_year(year);
+    let heshvan = if length % 10 == 5 { 30 } else { 29 };
+    let kislev = if length % 10 == 3 { 29 } else { 30 };
+    let mut months = vec![30, heshvan, kislev, 29, 30];
+    if is_hebrew_leap_year(year) {
+        months.extend([30, 29]);
+    } else {
+        months.push(29);
+    }
+    months.extend([30, 29, 30, 29, 30, 29]);
+    months
+}
+
+/// 1 Tishri of `year` as days since 1970-01-01.
+fn hebrew_new_year(year: i32) -> i64 {
+    HEBREW_EPOCH + hebrew_elapsed_days(year) + hebrew_year_delay(year)
+}
+
+/// Days from the epoch to the molad of Tishri, postponed by the rule
+/// that keeps Rosh Hashanah off Sunday, Wednesday and Friday.
+fn hebrew_elapsed_days(year: i32) -> i64 {
+    let months = (235 * i64::from(year) - 234).div_euclid(19);
+    let parts = 12_084 + 13_753 * months;
+    let days = 29 * months + parts.div_euclid(25_920);
+    if (3 * (days + 1)).rem_euclid(7) < 3 {
+        days + 1
+    } else {
+        days
+    }
+}
+
+/// The further postponement that keeps years between 353 and 385 days.
+fn hebrew_year_delay(year: i32) -> i64 {
+    let last = hebrew_elapsed_days(year - 1);
+    let current = hebrew_elapsed_days(year);
+    let next = hebrew_elapsed_days(year + 1);
+    if next - current == 356 {
+        2
+    } else if current - last == 382 {
+        1
+    } else {
+        0
+    }
+}
+
+/// A date of the tabular Hijri calendar for days since 1970-01-01.
+fn hijri_from_days(days: i64) -> (i32, u8, u8) {
+    let elapsed = days - HIJRI_EPOCH;
+    let mut year = (30 * elapsed).div_euclid(HIJRI_CYCLE_DAYS) + 1;
+    while hijri_new_year(year + 1) <= days {
+        year += 1;
+    }
+    while hijri_new_year(year) > days {
+        year -= 1;
+    }
+    let mut remaining = days - hijri_new_year(year);
+    let mut month = 1;
+    // Months alternate between 30 and 29 days; a leap year's 12th has 30.
+    while month < 12 && remaining >= 30 - (month as i64 + 1) % 2 {
+        remaining -= 30 - (month as i64 + 1) % 2;
+        month += 1;
+    }
+    (year as i32, month, remaining as u8 + 1)
+}
+
+/// Days since 1970-01-01 of a tabular Hijri date, or `None` for days the
+/// month does not have.
+pub(super) fn hijri_to_days(year: i32, month: u8, day: u8) -> Option<i64> {
+    if !(1..=12).contains(&month) || day < 1 {
+        return None;
+    }
+    let year = i64::from(year);
+    let length = match month {
+        12 if HIJRI_LEAP_YEARS.contains(&(year.rem_euclid(30))) => 30,
+        _ => 30 - (i64::from(month) + 1) % 2,
+    };
+    if i64::from(day) > length {
+        return None;
+    }
+    let month = i64::from(month);
+    Some(hijri_new_year(year) + 29 * (month - 1) + month / 2 + i64::from(day) - 1)
+}
+
+/// 1 Muharram of a tabular Hijri year as days since 1970-01-01.
+fn hijri_new_year(year: i64) -> i64 {
+    let cycle = (year - 1).div_euclid(30);
+    let position = (year - 1).rem_euclid(30);
+    let leap_days = HIJRI_LEAP_YEARS
+        .iter()
+        .filter(|leap| **leap <= position)
+        .count() as i64;
+    HIJRI_EPOCH + cycle * HIJRI_CYCLE_DAYS + position * 354 + leap_days
+}
+
+/// An Umm al-Qura date for days since 1970-01-01, or `None` outside the
+/// years the table covers.
+fn umalqura_from_days(days: i64) -> Option<(i32, u8, u8)> {
+    let mut remaining = days - UMALQURA_START;
+    if remaining < 0 {
+        return None;
+    }
+    for (idx, months) in UMALQURA_MONTHS.iter().enumerate() {
+        for month in 0..12 {
+            let length = 29 + i64::from(months >> month & 1);
+            if remaining < length {
+                let year = UMALQURA_FIRST_YEAR + idx as i32;
+                return Some((year, month as u8 + 1, remaining as u8 + 1));
+            }
+            remaining -= length;
+        }
+    }
+    None
+}
+
+/// Days since 1970-01-01 of an Umm al-Qura date, or `None` for dates the
+/// table does not hold.
+pub(super) fn umalqura_to_days(year: i32, month: u8, day: u8) -> Option<i64> {
+    let idx = usize::try_from(year - UMALQURA_FIRST_YEAR).ok()?;
+    let months = *UMALQURA_MONTHS.get(idx)?;
+    if !(1..=12).contains(&month) || day < 1 || day > 29 + (months >> (month - 1) & 1) as u8 {
+        return None;
+    }
+    let before: i64 = UMALQURA_MONTHS[..idx]
+        .iter()
+        .map(|year| 348 + i64::from(year.count_ones()))
+        .sum();
+    let within: i64 = (0..month - 1)
+        .map(|month| 29 + i64::from(months >> month & 1))
+        .sum();
+    Some(UMALQURA_START + before + within + i64::from(day) - 1)
+}
+
+const HEBREW_MONTHS: [&str; 12] = [
+    "תשרי", "חשון", "כסלו", "טבת", "שבט", "אדר", "ניסן", "אייר", "סיון", "תמוז", "אב", "אלול",
+];
+
+/// The name of a Hebrew month as Windows writes it, with Adar split into
+/// Adar I and Adar II in leap years.
+fn hebrew_month_name(year: i32, month: u8) -> &'static str {
+    let idx = usize::from(month.clamp(1, 13) - 1);
+    if !is_hebrew_leap_year(year) {
+        return HEBREW_MONTHS[idx.min(11)];
+    }
+    match idx {
+        0..5 => HEBREW_MONTHS[idx],
+        5 => "אדר א",
+        6 => "אדר ב",
+        _ => HEBREW_MONTHS[idx - 1],
+    }
+}
+
+/// Writes `number` in Hebrew letters the way Windows does for Hebrew
+/// dates: thousands of years are dropped, 15 and 16 are written 9+6 and
+/// 9+7, and a geresh or gershayim marks the numeral (`ה'`, `תשפ"ד`).
+fn hebrew_numeral(number: i32) -> String {
+    const HUNDREDS: [char; 4] = ['ק', 'ר', 'ש', 'ת'];
+    const TENS: [char; 9] = ['י', 'כ', 'ל', 'מ', 'נ', 'ס', 'ע', 'פ', 'צ'];
+    const ONES: [char; 9] = ['א', 'ב', 'ג', 'ד', 'ה', 'ו', 'ז', 'ח', 'ט'];
+
+    let mut rest = number.rem_euclid(1000);
+    if rest == 0 {
+        return number.to_string();
+    }
+    let mut letters = Vec::new();
+    while rest >= 400 {
+        letters.push('ת');
+        rest -= 400;
+    }
+    if rest >= 100 {
+        letters.push(HUNDREDS[(rest / 100 - 1) as usize]);
+        rest %= 100;
+    }
+    match rest {
+        15 => letters.extend(['ט', 'ו']),
+        16 => letters.extend(['ט', 'ז']),
+        _ => {
+            if rest >= 10 {
+                letters.push(TENS[(rest / 10 - 1) as usize]);
+            }
+            if rest % 10 > 0 {
+                letters.push(ONES[(rest % 10 - 1) as usize]);
+            }
+        }
+    }
+    let Some((last, init)) = letters.split_last() else {
+        return String::new();
+    };
+    let mut out: String = init.iter().collect();
+    if init.is_empty() {
+        out.push(*last);
+        out.push('\'');
+    } else {
+        out.push('"');
+        out.push(*last);
+    }
+    out
+}
@@ -0,0 +1,84 @@
+//! Patterns parsed once and formatted many times.
+
+use std::sync::Arc;
+
+use crate::parser::model::Pattern;
+use crate::parser::parse_pattern;
+
+use super::{
+    ColorValue, FormatterError, build_error_pattern, color_value, format_value,
+    locale::Locale,
+    locale_for,
+    options::FormatterOptions,
+    resolved::{ResolvedSection, resolve_sections},
+    value::FormatValue,
+};
+
+/// A pattern parsed together with its options, resolved locale and the
+/// calendars and numeral systems its sections use.
+///
+/// Formatting through a handle skips the pattern cache and the locale,
+/// calendar and numeral system registries, so it takes no locks and can
+/// be shared freely between threads.
+#[derive(Debug, Clone)]
+pub struct CompiledFormat {
+    pattern: Arc<Pattern>,
+    options: FormatterOptions,
+    locale: Arc<Locale>,
+    sections: Arc<[ResolvedSection]>,
+}
+
+impl CompiledFormat {
+    /// Parses `pattern` and resolves its locale. Invalid patterns are an
+    /// error when `options.throws` is set and otherwise render as the error
+    /// message, as they do with `format_with_options`.
+    pub fn new(pattern: &str, options: FormatterOptions) -> Result<Self, FormatterError> {
+        let parsed = match parse_pattern(pattern) {
+            Ok(parsed) => Arc::new(parsed),
+            Err(err) if options.throws => return Err(FormatterError::Parse(err)),
+            Err(err) => build_error_pattern(pattern, &err.to_string()),
+        };
+        Ok(Self::from_pattern(parsed, options))
+    }
+
+    /// Wraps an already parsed pattern.
+    pub fn from_pattern(pattern: Arc<Pattern>, options: FormatterOptions) -> Self {
+        let locale = locale_for(&pattern, &options);
+        let sections = resolve_sections(&pattern, &options).into();
+        Self {
+            pattern,
+            options,
+            locale,
+            sections,
+        }
+    }
+
+    pub fn pattern(&self) -> &Arc<Pattern> {
+        &self.pattern
+    }
+
+    pub fn options(&self) -> &FormatterOptions {
+        &self.options
+    }
+
+    pub fn format<'a, V>(&self, value: V) -> Result<String, FormatterError>
+    where
+        V: Into<FormatValue<'a>>,
+    {
+        format_value(
+            &self.pattern,
+            &self.sections,
+            value.into(),
+            &self.options,
+            &self.locale,
+        )
+    }
+
+    /// The color the pattern gives `value`, as `format_color` reports it.
+    pub fn color<'a, V>(&self, value: V) -> Option<ColorValue>
+    where
+        V: Into<FormatValue<'a>>,
+    {
+        color_value(&self.pattern, &value.into(), &self.options)
+    }
+}
@@ -0,0 +1,138 @@
+//! Era tables for the `g` (era name) and `e` (year of era) date tokens.
+
+use std::sync::OnceLock;
+
+use crate::parser::model::CalendarKind;
+
+use super::locale::locale_language;
+use super::options::FormatterOptions;
+
+/// One era of an era table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Era {
+    /// Full name, written by `ggg`: `令和`.
+    pub name: String,
+    /// Short name, written by `gg`: `令`.
+    pub short_name: String,
+    /// Latin abbreviation, written by `g`: `R`.
+    pub abbreviation: String,
+    /// The first day of the era as year, month and day.
+    pub start: (i32, u8, u8),
+}
+
+impl Era {
+    pub fn new(
+        name: impl Into<String>,
+        short_name: impl Into<String>,
+        abbreviation: impl Into<String>,
+        start: (i32, u8, u8),
+    ) -> Self {
+        Self {
+            name: name.into(),
+            short_name: short_name.into(),
+            abbreviation: abbreviation.into(),
+            start,
+        }
+    }
+}
+
+/// The eras a calendar counts years in, ordered by their first day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EraTable {
+    eras: Vec<Era>,
+}
+
+static JAPANESE: OnceLock<EraTable> = OnceLock::new();
+static MINGUO: OnceLock<EraTable> = OnceLock::new();
+static KOREAN: OnceLock<EraTable> = OnceLock::new();
+static THAI: OnceLock<EraTable> = OnceLock::new();
+
+impl EraTable {
+    pub fn new(eras: impl IntoIterator<Item = Era>) -> Self {
+        let mut eras: Vec<Era> = eras.into_iter().collect();
+        eras.sort_by_key(|era| era.start);
+        Self { eras }
+    }
+
+    /// The Japanese imperial eras from Meiji to Reiwa, as Windows dates
+    /// them.
+    pub fn japanese() -> Self {
+        Self::new([
+            Era::new("明治", "明", "M", (1868, 9, 8)),
+            Era::new("大正", "大", "T", (1912, 7, 30)),
+            Era::new("昭和", "昭", "S", (1926, 12, 25)),
+            Era::new("平成", "平", "H", (1989, 1, 8)),
+            Era::new("令和", "令", "R", (2019, 5, 1)),
+        ])
+    }
+
+    /// The Republic of China calendar used in Taiwan, counting from 1912.
+    pub fn minguo() -> Self {
+        Self::new([Era::new("中華民國", "民國", "民國", (1912, 1, 1))])
+    }
+
+    /// Korean Tangun years, counting from 2333 BC.
+    pub fn korean() -> Self {
+        Self::new([Era::new("단기", "단기", "단기", (-2332, 1, 1))])
+    }
+
+    /// Thai Buddhist years, counting from 543 BC.
+    pub fn thai() -> Self {
+        Self::new([Era::new("พ.ศ.", "พ.ศ.", "พ.ศ.", (-542, 1, 1))])
+    }
+
+    pub fn eras(&self) -> &[Era] {
+        &self.eras
+    }
+
+    /// The era a date falls in and the year within it, the era's first
+    /// year being 1. Dates before the first era have none.
+    pub fn era_for(&self, year: i32, month: u8, day: u8) -> Option<(&Era, i32)> {
+        let era = self
+            .eras
+            .iter()
+            .rev()
+            .find(|era| era.start <= (year, month, day))?;
+        Some((era, year - era.start.0 + 1))
+    }
+}
+
+/// The era table a locale tag uses by default: Japanese eras for Japanese
+/// and Minguo years for Taiwan.
+fn default_era_table(tag: &str) -> Option<&'static EraTable> {
+    let (language, region) = locale_language(tag)?;
+    match (language.as_str(), region.as_deref()) {
+        ("ja", _) => calendar_era_table(CalendarKind::Japanese),
+        ("zh", Some("TW")) => calendar_era_table(CalendarKind::Taiwan),
+        _ => None,
+    }
+}
+
+/// The eras a calendar counts its years in, if it counts in eras.
+pub(super) fn calendar_era_table(calendar: CalendarKind) -> Option<&'static EraTable> {
+    match calendar {
+        CalendarKind::Japanese => Some(JAPANESE.get_or_init(EraTable::japanese)),
+        CalendarKind::Taiwan => Some(MINGUO.get_or_init(EraTable::minguo)),
+        CalendarKind::Korean => Some(KOREAN.get_or_init(EraTable::korean)),
+        CalendarKind::Thai => Some(THAI.get_or_init(EraTable::thai)),
+        _ => None,
+    }
+}
+
+/// The era table for a section: the one in `options`, else the one its
+/// calendar counts in, else the default for the section's locale tag or
+/// the options' locale.
+pub(super) fn era_table_for<'a>(
+    options: &'a FormatterOptions,
+    calendar: CalendarKind,
+    tag: Option<&str>,
+) -> Option<&'a EraTable> {
+    if let Some(table) = &options.era_table {
+        return Some(table);
+    }
+    if let Some(table) = calendar_era_table(calendar) {
+        return Some(table);
+    }
+    let tag = tag.or((!options.locale.is_empty()).then_some(options.locale.as_str()))?;
+    default_era_table(tag)
+}
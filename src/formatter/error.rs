@@ -6,8 +6,12 @@ use crate::parser::error::ParseError;
 pub enum FormatterError {
     Parse(ParseError),
     DateOutOfBounds,
+    /// The serial has no date in the section's calendar.
+    InvalidDate,
     InvalidPattern(String),
     InvalidLocale(String),
+    /// A date names a zone id the time zone database does not know.
+    UnknownTimeZone(String),
     BigIntOverflow,
     Other(String),
 }
@@ -17,8 +21,10 @@ impl fmt::Display for FormatterError {
         match self {
             FormatterError::Parse(err) => write!(f, "{}", err),
             FormatterError::DateOutOfBounds => write!(f, "Date out of bounds"),
+            FormatterError::InvalidDate => write!(f, "Date not valid in its calendar"),
             FormatterError::InvalidPattern(pat) => write!(f, "Invalid pattern: {pat}"),
             FormatterError::InvalidLocale(tag) => write!(f, "Invalid locale: {tag}"),
+            FormatterError::UnknownTimeZone(id) => write!(f, "Unknown time zone: {id}"),
             FormatterError::BigIntOverflow => write!(f, "BigInt value out of range"),
             FormatterError::Other(msg) => write!(f, "{msg}"),
         }
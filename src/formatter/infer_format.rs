@@ -0,0 +1,144 @@
+//! Spreadsheet-style cell entry: turning typed text into a value plus the
+//! format code that keeps it rendering the way it was typed.
+
+use std::borrow::Cow;
+
+use super::{
+    locale::{Locale, locale_for_tag},
+    options::FormatterOptions,
+    parse_date::parse_date,
+    parse_number::{NumberScan, scan_number},
+    parse_time::scan_time,
+    value::FormatValue,
+};
+
+/// Infers the value and format code for text typed into a cell, the way
+/// Excel does on entry.
+///
+/// `"15%"` becomes `0.15` with `0%`, `"$1,000"` becomes `1000` with
+/// `$#,##0`, `"(1,200)"` becomes `-1200` with `#,##0_);(#,##0)`, `"1.5E+3"`
+/// becomes `1500` with `0.00E+00` and `"0 1/2"` becomes `0.5` with `# ?/?`.
+/// Dates and times such as `"3-Mar"`, `"1/2"` or `"13:05"` become serials
+/// with a code matching how they were written, dates without a year taking
+/// `options.reference_year`, and the locale's boolean names become
+/// booleans. Anything else is kept as text with `General`.
+///
+/// Every returned pattern is accepted by `parse_pattern`. Formatting the
+/// value with it in the same locale renders the typed text, except that
+/// decimals settle on two places as in Excel (`"12.5%"` shows as `12.50%`).
+pub fn infer_value_and_format(
+    text: &str,
+    options: &FormatterOptions,
+) -> (FormatValue<'static>, String) {
+    let table = &locale_for_tag(Some(options.locale.as_str()), options);
+    let trimmed = text.trim();
+
+    if trimmed.is_empty() {
+        return (FormatValue::Null, "General".to_string());
+    }
+    if let Some(scan) = scan_number(trimmed, table, &options.grouping)
+        && let Some(pattern) = number_pattern(&scan)
+    {
+        return (scan.value, pattern);
+    }
+    if let Some(inferred) = infer_fraction(trimmed) {
+        return inferred;
+    }
+    if let Some((_, serial, pattern)) = parse_date(trimmed, options) {
+        return (FormatValue::Number(serial), pattern);
+    }
+    if let Some(scan) = scan_time(trimmed, table) {
+        return (FormatValue::Number(scan.fraction()), scan.pattern);
+    }
+    if let Some(flag) = match_boolean(trimmed, table) {
+        return (FormatValue::Boolean(flag), "General".to_string());
+    }
+    (
+        FormatValue::Text(Cow::Owned(text.to_string())),
+        "General".to_string(),
+    )
+}
+
+/// The code for a typed number, or `None` for text Excel keeps as text:
+/// currency percentages such as `"$5%"`, which no single code renders,
+/// trailing minus signs as in `"12-"`, and NaN and infinities.
+fn number_pattern(scan: &NumberScan) -> Option<String> {
+    if scan.trailing_minus || matches!(scan.value, FormatValue::Number(n) if !n.is_finite()) {
+        return None;
+    }
+    if scan.exponential {
+        return Some("0.00E+00".to_string());
+    }
+    if scan.currency.is_some() && scan.percent {
+        return None;
+    }
+    let decimals = if scan.decimals > 0 { ".00" } else { "" };
+
+    let body = if let Some(symbol) = &scan.currency {
+        // "$" is a literal in format codes, other symbols go in a currency
+        // tag the way Excel writes them.
+        let symbol = if symbol == "$" {
+            symbol.clone()
+        } else {
+            format!("[${symbol}]")
+        };
+        let space = if scan.currency_space { " " } else { "" };
+        if scan.currency_after {
+            format!("#,##0{decimals}{space}{symbol}")
+        } else {
+            format!("{symbol}{space}#,##0{decimals}")
+        }
+    } else if scan.percent {
+        let digits = if scan.grouping { "#,##0" } else { "0" };
+        format!("{digits}{decimals}%")
+    } else if scan.grouping {
+        format!("#,##0{decimals}")
+    } else if scan.parens {
+        format!("0{decimals}")
+    } else {
+        return Some("General".to_string());
+    };
+
+    if scan.parens {
+        Some(format!("{body}_);({body})"))
+    } else {
+        Some(body)
+    }
+}
+
+/// Reads mixed fractions such as `"1 3/4"`. A bare `"1/2"` is a date, as it
+/// is in Excel.
+fn infer_fraction(text: &str) -> Option<(FormatValue<'static>, String)> {
+    let (negative, rest) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let (whole, fraction) = rest.split_once(' ')?;
+    let (numerator, denominator) = fraction.trim_start().split_once('/')?;
+    if [whole, numerator, denominator]
+        .iter()
+        .any(|part| part.is_empty() || !part.chars().all(|c| c.is_ascii_digit()))
+    {
+        return None;
+    }
+    let denominator_value: f64 = denominator.parse().ok()?;
+    if denominator_value == 0.0 || denominator.len() > 3 {
+        return None;
+    }
+    let value = whole.parse::<f64>().ok()? + numerator.parse::<f64>().ok()? / denominator_value;
+    let width = "?".repeat(denominator.len());
+    Some((
+        FormatValue::Number(if negative { -value } else { value }),
+        format!("# {width}/{width}"),
+    ))
+}
+
+fn match_boolean(text: &str, locale: &Locale) -> Option<bool> {
+    if text.eq_ignore_ascii_case(locale.bool_true()) {
+        Some(true)
+    } else if text.eq_ignore_ascii_case(locale.bool_false()) {
+        Some(false)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,94 @@
+use chrono::{
+    DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeDelta, TimeZone, Timelike,
+    Utc,
+};
+
+use super::{FromSerial, date_value, serial_duration, serial_to_civil, serial_to_instant};
+use crate::formatter::{
+    options::FormatterOptions,
+    value::{DateValue, DurationValue, FormatValue},
+};
+
+impl From<NaiveDateTime> for DateValue {
+    fn from(value: NaiveDateTime) -> Self {
+        date_value(
+            (value.year(), value.month(), value.day()),
+            (value.hour(), value.minute(), value.second()),
+            value.nanosecond(),
+        )
+    }
+}
+
+impl From<NaiveDate> for DateValue {
+    fn from(value: NaiveDate) -> Self {
+        DateValue::new(value.year())
+            .with_month(value.month() as u8)
+            .with_day(value.day() as u8)
+    }
+}
+
+/// Keeps the local wall clock and the offset in effect at that instant.
+impl<Tz: TimeZone> From<DateTime<Tz>> for DateValue {
+    fn from(value: DateTime<Tz>) -> Self {
+        let offset = value.offset().fix().local_minus_utc();
+        DateValue::from(value.naive_local()).with_offset(offset)
+    }
+}
+
+impl<'a> From<NaiveDateTime> for FormatValue<'a> {
+    fn from(value: NaiveDateTime) -> Self {
+        FormatValue::Date(value.into())
+    }
+}
+
+impl<'a> From<NaiveDate> for FormatValue<'a> {
+    fn from(value: NaiveDate) -> Self {
+        FormatValue::Date(value.into())
+    }
+}
+
+impl<'a, Tz: TimeZone> From<DateTime<Tz>> for FormatValue<'a> {
+    fn from(value: DateTime<Tz>) -> Self {
+        FormatValue::Date(value.into())
+    }
+}
+
+impl<'a> From<TimeDelta> for FormatValue<'a> {
+    fn from(value: TimeDelta) -> Self {
+        FormatValue::Duration(DurationValue::from_secs_nanos(
+            value.num_seconds(),
+            value.subsec_nanos(),
+        ))
+    }
+}
+
+impl FromSerial for NaiveDateTime {
+    fn from_serial(serial: f64, options: &FormatterOptions) -> Option<Self> {
+        let civil = serial_to_civil(serial, options)?;
+        let date = NaiveDate::from_ymd_opt(civil.year, civil.month, civil.day)?;
+        let time =
+            NaiveTime::from_hms_nano_opt(civil.hour, civil.minute, civil.second, civil.nanosecond)?;
+        Some(date.and_time(time))
+    }
+}
+
+impl FromSerial for NaiveDate {
+    fn from_serial(serial: f64, options: &FormatterOptions) -> Option<Self> {
+        let civil = serial_to_civil(serial, options)?;
+        NaiveDate::from_ymd_opt(civil.year, civil.month, civil.day)
+    }
+}
+
+impl FromSerial for DateTime<Utc> {
+    fn from_serial(serial: f64, options: &FormatterOptions) -> Option<Self> {
+        let (seconds, nanoseconds, _) = serial_to_instant(serial, options)?;
+        DateTime::from_timestamp(seconds, nanoseconds)
+    }
+}
+
+impl FromSerial for TimeDelta {
+    fn from_serial(serial: f64, _options: &FormatterOptions) -> Option<Self> {
+        let (seconds, nanoseconds) = serial_duration(serial)?;
+        TimeDelta::try_seconds(seconds)?.checked_add(&TimeDelta::nanoseconds(nanoseconds.into()))
+    }
+}
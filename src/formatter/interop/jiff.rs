@@ -0,0 +1,136 @@
+use jiff::{
+    SignedDuration, Timestamp, Zoned,
+    civil::{Date, DateTime},
+    tz::{Offset, TimeZone},
+};
+
+use super::{FromSerial, date_value, serial_duration, serial_to_civil, serial_to_instant};
+use crate::formatter::{
+    options::FormatterOptions,
+    value::{DateValue, DurationValue, FormatValue},
+};
+
+impl From<DateTime> for DateValue {
+    fn from(value: DateTime) -> Self {
+        date_value(
+            (
+                value.year().into(),
+                value.month() as u32,
+                value.day() as u32,
+            ),
+            (
+                value.hour() as u32,
+                value.minute() as u32,
+                value.second() as u32,
+            ),
+            value.subsec_nanosecond() as u32,
+        )
+    }
+}
+
+impl From<Date> for DateValue {
+    fn from(value: Date) -> Self {
+        DateValue::new(value.year().into())
+            .with_month(value.month() as u8)
+            .with_day(value.day() as u8)
+    }
+}
+
+/// Keeps the local wall clock and the offset in effect at that instant.
+impl From<Zoned> for DateValue {
+    fn from(value: Zoned) -> Self {
+        DateValue::from(value.datetime()).with_offset(value.offset().seconds())
+    }
+}
+
+/// The UTC wall clock of the instant.
+impl From<Timestamp> for DateValue {
+    fn from(value: Timestamp) -> Self {
+        DateValue::from(value.to_zoned(TimeZone::UTC))
+    }
+}
+
+impl<'a> From<DateTime> for FormatValue<'a> {
+    fn from(value: DateTime) -> Self {
+        FormatValue::Date(value.into())
+    }
+}
+
+impl<'a> From<Date> for FormatValue<'a> {
+    fn from(value: Date) -> Self {
+        FormatValue::Date(value.into())
+    }
+}
+
+impl<'a> From<Zoned> for FormatValue<'a> {
+    fn from(value: Zoned) -> Self {
+        FormatValue::Date(value.into())
+    }
+}
+
+impl<'a> From<Timestamp> for FormatValue<'a> {
+    fn from(value: Timestamp) -> Self {
+        FormatValue::Date(value.into())
+    }
+}
+
+impl<'a> From<SignedDuration> for FormatValue<'a> {
+    fn from(value: SignedDuration) -> Self {
+        FormatValue::Duration(DurationValue::from_secs_nanos(
+            value.as_secs(),
+            value.subsec_nanos(),
+        ))
+    }
+}
+
+impl FromSerial for DateTime {
+    fn from_serial(serial: f64, options: &FormatterOptions) -> Option<Self> {
+        let civil = serial_to_civil(serial, options)?;
+        DateTime::new(
+            i16::try_from(civil.year).ok()?,
+            civil.month as i8,
+            civil.day as i8,
+            civil.hour as i8,
+            civil.minute as i8,
+            civil.second as i8,
+            civil.nanosecond as i32,
+        )
+        .ok()
+    }
+}
+
+impl FromSerial for Date {
+    fn from_serial(serial: f64, options: &FormatterOptions) -> Option<Self> {
+        let civil = serial_to_civil(serial, options)?;
+        Date::new(
+            i16::try_from(civil.year).ok()?,
+            civil.month as i8,
+            civil.day as i8,
+        )
+        .ok()
+    }
+}
+
+impl FromSerial for Timestamp {
+    fn from_serial(serial: f64, options: &FormatterOptions) -> Option<Self> {
+        let (seconds, nanoseconds, _) = serial_to_instant(serial, options)?;
+        Timestamp::new(seconds, nanoseconds as i32).ok()
+    }
+}
+
+/// Comes back at the offset `options.time_zone` has at that instant.
+impl FromSerial for Zoned {
+    fn from_serial(serial: f64, options: &FormatterOptions) -> Option<Self> {
+        let (seconds, nanoseconds, offset) = serial_to_instant(serial, options)?;
+        let offset = Offset::from_seconds(offset).ok()?;
+        let timestamp = Timestamp::new(seconds, nanoseconds as i32).ok()?;
+        Some(timestamp.to_zoned(TimeZone::fixed(offset)))
+    }
+}
+
+impl FromSerial for SignedDuration {
+    fn from_serial(serial: f64, _options: &FormatterOptions) -> Option<Self> {
+        let (seconds, nanoseconds) = serial_duration(serial)?;
+        Some(SignedDuration::new(seconds, nanoseconds))
+    }
+}
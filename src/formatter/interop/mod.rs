@@ -0,0 +1,107 @@
+//! Conversions between format values and the date and duration types of
+//! `chrono`, `time` and `jiff`, each behind the cargo feature of the same
+//! name.
+//!
+//! Date-times become `DateValue`s keeping their wall clock, nanoseconds and
+//! UTC offset, and durations become `DurationValue`s. `FromSerial` goes the
+//! other way.
+
+#[cfg(feature = "chrono")]
+mod chrono;
+#[cfg(feature = "jiff")]
+mod jiff;
+#[cfg(feature = "time")]
+mod time;
+
+use crate::constants::{EPOCH_1900, EPOCH_1904};
+
+use super::{
+    options::FormatterOptions,
+    serial::{days_from_civil, days_in_month},
+    value::DateValue,
+};
+
+const DAY_MS: i64 = 86_400_000;
+
+/// Date, time and duration types that can be read back from a serial.
+pub trait FromSerial: Sized {
+    /// Reads `serial` in the date system `options` selects, to the
+    /// millisecond. Zone-aware types take the serial as wall-clock time in
+    /// `options.time_zone`, or UTC without one. Durations read it as a
+    /// number of days.
+    fn from_serial(serial: f64, options: &FormatterOptions) -> Option<Self>;
+}
+
+/// Wall-clock fields read off a serial.
+struct Civil {
+    year: i32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    nanosecond: u32,
+}
+
+fn date_value(
+    (year, month, day): (i32, u32, u32),
+    (hour, minute, second): (u32, u32, u32),
+    nanosecond: u32,
+) -> DateValue {
+    DateValue::new(year)
+        .with_month(month as u8)
+        .with_day(day as u8)
+        .with_time(hour as u8, minute as u8, second as u8)
+        .with_nanosecond(nanosecond.min(999_999_999))
+}
+
+/// A number of days as whole seconds and nanoseconds with the same sign.
+fn serial_duration(serial: f64) -> Option<(i64, i32)> {
+    let ms = (serial * DAY_MS as f64).round();
+    if !ms.is_finite() || ms.abs() > 1e17 {
+        return None;
+    }
+    let ms = ms as i64;
+    Some((ms / 1000, (ms % 1000) as i32 * 1_000_000))
+}
+
+/// The wall clock `serial` shows in the date system `options` selects,
+/// read by `DateValue::from_serial` so it matches the formatter. The
+/// phantom 1900-02-29 has no such time and gives `None`.
+fn serial_to_civil(serial: f64, options: &FormatterOptions) -> Option<Civil> {
+    let system = if options.date_1904 {
+        EPOCH_1904
+    } else {
+        EPOCH_1900
+    };
+    let date = DateValue::from_serial(serial, system, options.leap_1900).ok()?;
+    let (month, day) = (date.month?, date.day?);
+    if day > days_in_month(date.year, month) {
+        return None;
+    }
+    Some(Civil {
+        year: date.year,
+        month: month.into(),
+        day: day.into(),
+        hour: date.hour?.into(),
+        minute: date.minute?.into(),
+        second: date.second?.into(),
+        nanosecond: u32::from(date.millisecond.unwrap_or(0)) * 1_000_000,
+    })
+}
+
+/// The instant `serial` names, as seconds since 1970 and nanoseconds, and
+/// the offset of `options.time_zone` at that instant.
+fn serial_to_instant(serial: f64, options: &FormatterOptions) -> Option<(i64, u32, i32)> {
+    let civil = serial_to_civil(serial, options)?;
+    let days = days_from_civil(civil.year, civil.month, civil.day);
+    let local = days * 86_400 + i64::from(civil.hour * 3_600 + civil.minute * 60 + civil.second);
+    let nanos = civil.nanosecond;
+    match &options.time_zone {
+        Some(zone) => {
+            let utc = zone.to_utc(local)?;
+            Some((utc, nanos, zone.offset_at(utc)?))
+        }
+        None => Some((local, nanos, 0)),
+    }
+}
@@ -0,0 +1,111 @@
+use time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+use super::{FromSerial, date_value, serial_duration, serial_to_civil, serial_to_instant};
+use crate::formatter::{
+    options::FormatterOptions,
+    value::{DateValue, DurationValue, FormatValue},
+};
+
+impl From<PrimitiveDateTime> for DateValue {
+    fn from(value: PrimitiveDateTime) -> Self {
+        date_value(
+            (
+                value.year(),
+                u8::from(value.month()).into(),
+                value.day().into(),
+            ),
+            (
+                value.hour().into(),
+                value.minute().into(),
+                value.second().into(),
+            ),
+            value.nanosecond(),
+        )
+    }
+}
+
+impl From<Date> for DateValue {
+    fn from(value: Date) -> Self {
+        DateValue::new(value.year())
+            .with_month(value.month().into())
+            .with_day(value.day())
+    }
+}
+
+/// Keeps the local wall clock and the offset.
+impl From<OffsetDateTime> for DateValue {
+    fn from(value: OffsetDateTime) -> Self {
+        let local = PrimitiveDateTime::new(value.date(), value.time());
+        DateValue::from(local).with_offset(value.offset().whole_seconds())
+    }
+}
+
+impl<'a> From<PrimitiveDateTime> for FormatValue<'a> {
+    fn from(value: PrimitiveDateTime) -> Self {
+        FormatValue::Date(value.into())
+    }
+}
+
+impl<'a> From<Date> for FormatValue<'a> {
+    fn from(value: Date) -> Self {
+        FormatValue::Date(value.into())
+    }
+}
+
+impl<'a> From<OffsetDateTime> for FormatValue<'a> {
+    fn from(value: OffsetDateTime) -> Self {
+        FormatValue::Date(value.into())
+    }
+}
+
+impl<'a> From<Duration> for FormatValue<'a> {
+    fn from(value: Duration) -> Self {
+        FormatValue::Duration(DurationValue::from_secs_nanos(
+            value.whole_seconds(),
+            value.subsec_nanoseconds(),
+        ))
+    }
+}
+
+impl FromSerial for PrimitiveDateTime {
+    fn from_serial(serial: f64, options: &FormatterOptions) -> Option<Self> {
+        let civil = serial_to_civil(serial, options)?;
+        let month = Month::try_from(civil.month as u8).ok()?;
+        let date = Date::from_calendar_date(civil.year, month, civil.day as u8).ok()?;
+        let time = Time::from_hms_nano(
+            civil.hour as u8,
+            civil.minute as u8,
+            civil.second as u8,
+            civil.nanosecond,
+        )
+        .ok()?;
+        Some(PrimitiveDateTime::new(date, time))
+    }
+}
+
+impl FromSerial for Date {
+    fn from_serial(serial: f64, options: &FormatterOptions) -> Option<Self> {
+        let civil = serial_to_civil(serial, options)?;
+        let month = Month::try_from(civil.month as u8).ok()?;
+        Date::from_calendar_date(civil.year, month, civil.day as u8).ok()
+    }
+}
+
+/// Comes back at the offset `options.time_zone` has at that instant.
+impl FromSerial for OffsetDateTime {
+    fn from_serial(serial: f64, options: &FormatterOptions) -> Option<Self> {
+        let (seconds, nanoseconds, offset) = serial_to_instant(serial, options)?;
+        let utc = OffsetDateTime::from_unix_timestamp(seconds)
+            .ok()?
+            .replace_nanosecond(nanoseconds)
+            .ok()?;
+        utc.checked_to_offset(UtcOffset::from_whole_seconds(offset).ok()?)
+    }
+}
+
+impl FromSerial for Duration {
+    fn from_serial(serial: f64, _options: &FormatterOptions) -> Option<Self> {
+        let (seconds, nanoseconds) = serial_duration(serial)?;
+        Some(Duration::new(seconds, nanoseconds))
+    }
+}
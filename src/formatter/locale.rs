@@ -1,9 +1,11 @@
 use std::collections::HashMap;
-use std::sync::{Mutex, OnceLock};
+use std::sync::{Arc, Mutex, OnceLock};
 
 use serde::Deserialize;
 use thiserror::Error;
 
+use super::options::FormatterOptions;
+
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
 pub enum LocaleError {
     #[error("invalid locale tag: {0}")]
@@ -31,31 +33,31 @@ pub struct LocaleSettings {
     pub prefer_mdy: Option<bool>,
 }
 
-impl LocaleSettings {
-    fn apply(self, base: &Locale) -> Locale {
-        Locale {
-            group: self.group.unwrap_or_else(|| base.group.clone()),
-            decimal: self.decimal.unwrap_or_else(|| base.decimal.clone()),
-            positive: self.positive.unwrap_or_else(|| base.positive.clone()),
-            negative: self.negative.unwrap_or_else(|| base.negative.clone()),
-            percent: self.percent.unwrap_or_else(|| base.percent.clone()),
-            exponent: self.exponent.unwrap_or_else(|| base.exponent.clone()),
-            nan: self.nan.unwrap_or_else(|| base.nan.clone()),
-            infinity: self.infinity.unwrap_or_else(|| base.infinity.clone()),
-            ampm: self.ampm.unwrap_or_else(|| base.ampm.clone()),
-            mmmm6: self.mmmm6.unwrap_or_else(|| base.mmmm6.clone()),
-            mmm6: self.mmm6.unwrap_or_else(|| base.mmm6.clone()),
-            mmmm: self.mmmm.unwrap_or_else(|| base.mmmm.clone()),
-            mmm: self.mmm.unwrap_or_else(|| base.mmm.clone()),
-            dddd: self.dddd.unwrap_or_else(|| base.dddd.clone()),
-            ddd: self.ddd.unwrap_or_else(|| base.ddd.clone()),
-            bool_values: self.bool_values.unwrap_or_else(|| base.bool_values.clone()),
-            prefer_mdy: self.prefer_mdy.unwrap_or(base.prefer_mdy),
+impl From<&Locale> for LocaleSettings {
+    fn from(locale: &Locale) -> Self {
+        Self {
+            group: Some(locale.group.clone()),
+            decimal: Some(locale.decimal.clone()),
+            positive: Some(locale.positive.clone()),
+            negative: Some(locale.negative.clone()),
+            percent: Some(locale.percent.clone()),
+            exponent: Some(locale.exponent.clone()),
+            nan: Some(locale.nan.clone()),
+            infinity: Some(locale.infinity.clone()),
+            ampm: Some(locale.ampm.clone()),
+            mmmm6: Some(locale.mmmm6.clone()),
+            mmm6: Some(locale.mmm6.clone()),
+            mmmm: Some(locale.mmmm.clone()),
+            mmm: Some(locale.mmm.clone()),
+            dddd: Some(locale.dddd.clone()),
+            ddd: Some(locale.ddd.clone()),
+            bool_values: Some(locale.bool_values.clone()),
+            prefer_mdy: Some(locale.prefer_mdy),
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Locale {
     pub group: String,
     pub decimal: String,
@@ -74,9 +76,38 @@ pub struct Locale {
     pub ddd: Vec<String>,
     pub bool_values: Vec<String>,
     pub prefer_mdy: bool,
+    /// Set only on the bundled default locale, which writes `A/P` as is
+    /// rather than taking the locale's AM/PM names.
+    pub is_default: bool,
 }
 
 impl Locale {
+    /// A copy of this locale with `settings` overriding its own values.
+    pub fn with_settings(&self, settings: LocaleSettings) -> Locale {
+        Locale {
+            group: settings.group.unwrap_or_else(|| self.group.clone()),
+            decimal: settings.decimal.unwrap_or_else(|| self.decimal.clone()),
+            positive: settings.positive.unwrap_or_else(|| self.positive.clone()),
+            negative: settings.negative.unwrap_or_else(|| self.negative.clone()),
+            percent: settings.percent.unwrap_or_else(|| self.percent.clone()),
+            exponent: settings.exponent.unwrap_or_else(|| self.exponent.clone()),
+            nan: settings.nan.unwrap_or_else(|| self.nan.clone()),
+            infinity: settings.infinity.unwrap_or_else(|| self.infinity.clone()),
+            ampm: settings.ampm.unwrap_or_else(|| self.ampm.clone()),
+            mmmm6: settings.mmmm6.unwrap_or_else(|| self.mmmm6.clone()),
+            mmm6: settings.mmm6.unwrap_or_else(|| self.mmm6.clone()),
+            mmmm: settings.mmmm.unwrap_or_else(|| self.mmmm.clone()),
+            mmm: settings.mmm.unwrap_or_else(|| self.mmm.clone()),
+            dddd: settings.dddd.unwrap_or_else(|| self.dddd.clone()),
+            ddd: settings.ddd.unwrap_or_else(|| self.ddd.clone()),
+            bool_values: settings
+                .bool_values
+                .unwrap_or_else(|| self.bool_values.clone()),
+            prefer_mdy: settings.prefer_mdy.unwrap_or(self.prefer_mdy),
+            is_default: false,
+        }
+    }
+
     pub fn bool_true(&self) -> &str {
         self.bool_values
             .first()
@@ -134,6 +165,8 @@ struct LocaleRaw {
     bool_values: Vec<String>,
     #[serde(default, rename = "preferMDY")]
     prefer_mdy: bool,
+    #[serde(default, rename = "isDefault")]
+    is_default: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -142,28 +175,54 @@ struct LocaleId {
     language: String,
 }
 
-struct LocaleRegistry {
-    default: &'static Locale,
-    locales: HashMap<String, &'static Locale>,
+/// A set of locales keyed by tag, starting from the bundled locale data.
+///
+/// The functions at the top of this module work on a shared process-wide
+/// registry; an owned one can be passed to the formatter through
+/// `FormatterOptions::locale_registry` instead.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocaleRegistry {
+    default: Arc<Locale>,
+    locales: HashMap<String, Arc<Locale>>,
 }
 
 static REGISTRY: OnceLock<Mutex<LocaleRegistry>> = OnceLock::new();
+static BUNDLED: OnceLock<LocaleRegistry> = OnceLock::new();
 static CODE_MAP: OnceLock<HashMap<u32, String>> = OnceLock::new();
 
-pub fn default_locale() -> &'static Locale {
-    registry().lock().expect("locale registry poisoned").default
+pub fn default_locale() -> Arc<Locale> {
+    registry()
+        .lock()
+        .expect("locale registry poisoned")
+        .default_locale()
 }
 
 fn registry() -> &'static Mutex<LocaleRegistry> {
-    REGISTRY.get_or_init(|| Mutex::new(LocaleRegistry::load()))
+    REGISTRY.get_or_init(|| Mutex::new(LocaleRegistry::new()))
+}
+
+pub fn get_locale(tag: Option<&str>) -> Option<Arc<Locale>> {
+    let tag = tag?;
+    let registry = registry().lock().expect("locale registry poisoned");
+    registry.get_locale(tag)
 }
 
-pub fn get_locale(tag: Option<&str>) -> Option<&'static Locale> {
-    tag.and_then(lookup_locale)
+pub fn get_locale_or_default(tag: Option<&str>) -> Arc<Locale> {
+    get_locale(tag).unwrap_or_else(default_locale)
 }
 
-pub fn get_locale_or_default(tag: Option<&str>) -> &'static Locale {
-    get_locale(tag).unwrap_or_else(|| default_locale())
+/// Picks the locale for `tag` the way `options` asks: its inline locale if
+/// it has one, then its own registry, then the shared one.
+pub(crate) fn locale_for_tag(tag: Option<&str>, options: &FormatterOptions) -> Arc<Locale> {
+    if let Some(locale) = &options.inline_locale {
+        return locale.clone();
+    }
+    match &options.locale_registry {
+        Some(registry) => tag
+            .and_then(|tag| registry.get_locale(tag))
+            .unwrap_or_else(|| registry.default_locale()),
+        None => get_locale_or_default(tag),
+    }
 }
 
 pub fn add_locale(settings: LocaleSettings, tag: impl AsRef<str>) -> Result<(), LocaleError> {
@@ -171,71 +230,141 @@ pub fn add_locale(settings: LocaleSettings, tag: impl AsRef<str>) -> Result<(),
     registry.add_locale(settings, tag.as_ref())
 }
 
-#[allow(dead_code)]
+pub fn remove_locale(tag: impl AsRef<str>) -> bool {
+    let mut registry = registry().lock().expect("locale registry poisoned");
+    registry.remove_locale(tag.as_ref())
+}
+
+pub fn list_locales() -> Vec<String> {
+    registry()
+        .lock()
+        .expect("locale registry poisoned")
+        .list_locales()
+}
+
+pub fn get_locale_settings(tag: impl AsRef<str>) -> Option<LocaleSettings> {
+    registry()
+        .lock()
+        .expect("locale registry poisoned")
+        .get_locale_settings(tag.as_ref())
+}
+
 pub fn resolve_locale(tag: &str) -> Option<String> {
     resolve_code(tag).or_else(|| parse_locale_tag(tag).map(|id| id.lang))
 }
 
-fn lookup_locale(tag: &str) -> Option<&'static Locale> {
-    if tag.trim().is_empty() {
-        return None;
+impl LocaleRegistry {
+    /// A registry holding the bundled locales.
+    pub fn new() -> Self {
+        BUNDLED.get_or_init(Self::load).clone()
     }
-    let registry = registry().lock().expect("locale registry poisoned");
-    registry.lookup(tag)
-}
 
-impl LocaleRegistry {
     fn load() -> Self {
         let raw: LocaleFile =
             serde_json::from_str(include_str!("./locales.json")).expect("invalid locale data");
 
-        let default = leak_locale(Locale::from_raw(raw.default));
+        let default = Arc::new(Locale::from_raw(raw.default));
         let mut locales = HashMap::new();
 
         for (key, value) in raw.locales {
             let canonical = canonicalize_key(&key);
-            locales.insert(canonical, leak_locale(Locale::from_raw(value)));
+            locales.insert(canonical, Arc::new(Locale::from_raw(value)));
         }
 
         Self { default, locales }
     }
 
-    fn lookup(&self, tag: &str) -> Option<&'static Locale> {
+    /// The locale used when a tag is missing or unknown.
+    pub fn default_locale(&self) -> Arc<Locale> {
+        self.default.clone()
+    }
+
+    /// Looks up a locale by tag (`de-AT`, `de_AT`, `de`) or by Windows
+    /// LCID in hex (`c07`), falling back from region to language.
+    pub fn get_locale(&self, tag: &str) -> Option<Arc<Locale>> {
+        if tag.trim().is_empty() {
+            return None;
+        }
         if let Some(code) = resolve_code(tag) {
             if let Some(loc) = self.locales.get(&code) {
-                return Some(*loc);
+                return Some(loc.clone());
             }
             if let Some(parsed) = parse_locale_tag(&code)
                 && let Some(loc) = self.locales.get(&parsed.language)
             {
-                return Some(*loc);
+                return Some(loc.clone());
             }
         }
         if let Some(parsed) = parse_locale_tag(tag) {
             if let Some(loc) = self.locales.get(&parsed.lang) {
-                return Some(*loc);
+                return Some(loc.clone());
             }
             if let Some(loc) = self.locales.get(&parsed.language) {
-                return Some(*loc);
+                return Some(loc.clone());
             }
         }
         None
     }
 
-    fn add_locale(&mut self, settings: LocaleSettings, tag: &str) -> Result<(), LocaleError> {
+    /// Adds or replaces the locale for `tag`, filling the unset settings
+    /// from the default locale. A regional tag also answers for its
+    /// language when nothing else does.
+    pub fn add_locale(&mut self, settings: LocaleSettings, tag: &str) -> Result<(), LocaleError> {
         let parsed =
             parse_locale_tag(tag).ok_or_else(|| LocaleError::InvalidTag(tag.to_string()))?;
-        let locale = leak_locale(settings.apply(self.default));
-        self.locales.insert(parsed.lang.clone(), locale);
-        if parsed.language != parsed.lang && !self.locales.contains_key(&parsed.language) {
-            self.locales.insert(parsed.language.clone(), locale);
+        let locale = Arc::new(self.default.with_settings(settings));
+        let replaced = self.locales.insert(parsed.lang.clone(), locale.clone());
+        if parsed.language != parsed.lang {
+            let stands_in = match (self.locales.get(&parsed.language), &replaced) {
+                (None, _) => true,
+                (Some(alias), Some(old)) => Arc::ptr_eq(alias, old),
+                (Some(_), None) => false,
+            };
+            if stands_in {
+                self.locales.insert(parsed.language.clone(), locale);
+            }
         }
         Ok(())
     }
+
+    /// Removes the locale registered under `tag`, along with the language
+    /// entry it was standing in for. Returns whether anything was removed.
+    pub fn remove_locale(&mut self, tag: &str) -> bool {
+        let Some(parsed) = parse_locale_tag(tag) else {
+            return false;
+        };
+        let Some(removed) = self.locales.remove(&parsed.lang) else {
+            return false;
+        };
+        if parsed.language != parsed.lang
+            && self
+                .locales
+                .get(&parsed.language)
+                .is_some_and(|alias| Arc::ptr_eq(alias, &removed))
+        {
+            self.locales.remove(&parsed.language);
+        }
+        true
+    }
+
+    /// The registered keys in canonical form (`de`, `de_AT`), sorted.
+    pub fn list_locales(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.locales.keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    /// The full settings of the locale `tag` resolves to.
+    pub fn get_locale_settings(&self, tag: &str) -> Option<LocaleSettings> {
+        self.get_locale(tag)
+            .map(|locale| LocaleSettings::from(locale.as_ref()))
+    }
 }
 
-fn leak_locale(locale: Locale) -> &'static Locale {
-    Box::leak(Box::new(locale))
+impl Default for LocaleRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Locale {
@@ -290,6 +419,7 @@ impl Locale {
             ddd: raw.ddd,
             bool_values: ensure_pair(raw.bool_values, ["TRUE", "FALSE"]),
             prefer_mdy: raw.prefer_mdy,
+            is_default: raw.is_default,
         }
     }
 }
@@ -313,7 +443,8 @@ fn parse_locale_tag(input: &str) -> Option<LocaleId> {
     if trimmed.is_empty() {
         return None;
     }
-    let head = trimmed.split('@').next().unwrap_or(trimmed);
+    // `th-TH,107` carries a calendar after the comma.
+    let head = trimmed.split([',', '@']).next().unwrap_or(trimmed);
     let head = head.split('.').next().unwrap_or(head);
     let mut parts = head.split(['-', '_']).filter(|part| !part.is_empty());
 
@@ -335,6 +466,18 @@ fn parse_locale_tag(input: &str) -> Option<LocaleId> {
     Some(LocaleId { lang, language })
 }
 
+/// The language and region a locale tag or hex LCID names, such as `ja`
+/// and `None` for `411` or `zh` and `TW` for `zh-TW`.
+pub(crate) fn locale_language(tag: &str) -> Option<(String, Option<String>)> {
+    let code = resolve_code(tag);
+    let parsed = parse_locale_tag(code.as_deref().unwrap_or(tag))?;
+    let region = parsed
+        .lang
+        .split_once('_')
+        .map(|(_, region)| region.to_string());
+    Some((parsed.language, region))
+}
+
 fn resolve_code(tag: &str) -> Option<String> {
     let trimmed = tag.trim();
     if trimmed.is_empty() {
@@ -342,7 +485,7 @@ fn resolve_code(tag: &str) -> Option<String> {
     }
     // drop leading currency or dash markers as needed
     let cleaned = trimmed.trim_start_matches('$').trim_start_matches('-');
-    let cleaned = cleaned.trim();
+    let cleaned = cleaned.split(',').next().unwrap_or(cleaned).trim();
     if cleaned.is_empty() {
         return None;
     }
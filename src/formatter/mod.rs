@@ -1,33 +1,66 @@
 use std::borrow::Cow;
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex, OnceLock};
+use std::sync::{Arc, OnceLock};
 
 use crate::constants::INDEX_COLORS;
 use crate::parser::model::{
     Color, ConditionOperator, Pattern, Section, SectionToken, Token, TokenKind, TokenValue,
 };
-use crate::parser::parse_pattern;
 use num_traits::{Signed, ToPrimitive};
 
+mod cache;
+mod calendar;
+mod compiled;
+mod era;
 pub mod error;
-mod chinese;
 mod general;
+mod infer_format;
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+mod interop;
 mod locale;
 mod math;
+mod numeral;
 pub mod options;
 mod pad;
+mod parse_date;
+mod parse_number;
+mod parse_time;
+mod parse_with_pattern;
+mod resolved;
 mod run_part;
-mod serial;
+pub mod serial;
+mod timezone;
 mod to_ymd;
 pub mod value;
 
+pub use cache::{
+    DEFAULT_PATTERN_CACHE_CAPACITY, PatternCacheStats, clear_pattern_cache, pattern_cache_stats,
+    set_pattern_cache_capacity,
+};
+pub use calendar::{Calendar, CalendarDay, CalendarError, add_calendar, remove_calendar};
+pub use compiled::CompiledFormat;
+pub use era::{Era, EraTable};
 pub use error::FormatterError;
-pub use locale::{LocaleError, LocaleSettings, add_locale, default_locale};
+pub use infer_format::infer_value_and_format;
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+pub use interop::FromSerial;
+pub use locale::{
+    Locale, LocaleError, LocaleRegistry, LocaleSettings, add_locale, default_locale,
+    get_locale_settings, list_locales, remove_locale,
+};
+pub use numeral::{BuiltinNumerals, NumeralSystem, add_numeral_system, remove_numeral_system};
 pub use options::FormatterOptions;
+pub use parse_date::parse_date;
+pub use parse_number::parse_number;
+pub use parse_time::parse_time;
+pub use parse_with_pattern::parse_with_pattern;
 pub use run_part::RunValue;
-pub use value::{DateValue, FormatValue};
+pub use serial::SerialError;
+pub use timezone::TimeZone;
+pub use value::{DateValue, DurationValue, FormatValue};
 
-use locale::get_locale_or_default;
+use cache::prepare_pattern;
+use locale::locale_for_tag;
+use resolved::{ResolvedSection, resolve_sections, resolved_section};
 use run_part::run_part;
 use serial::date_to_serial;
 
@@ -37,25 +70,8 @@ pub enum ColorValue {
     Index(u32),
 }
 
-struct CacheEntry {
-    value: CachedPattern,
-}
-
-enum CachedPattern {
-    Valid(Arc<Pattern>),
-    Invalid {
-        message: String,
-        fallback: Arc<Pattern>,
-    },
-}
-
-static PATTERN_CACHE: OnceLock<Mutex<HashMap<String, CacheEntry>>> = OnceLock::new();
 static DEFAULT_TEXT_SECTION: OnceLock<Arc<Section>> = OnceLock::new();
 
-fn pattern_cache() -> &'static Mutex<HashMap<String, CacheEntry>> {
-    PATTERN_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
-}
-
 fn default_text_section() -> Arc<Section> {
     DEFAULT_TEXT_SECTION
         .get_or_init(|| {
@@ -84,53 +100,6 @@ fn build_error_pattern(pattern: &str, error: &str) -> Arc<Pattern> {
     })
 }
 
-fn prepare_pattern(pattern: &str, should_throw: bool) -> Result<Arc<Pattern>, FormatterError> {
-    let mut cache = pattern_cache().lock().expect("pattern cache poisoned");
-    if let Some(entry) = cache.get(pattern) {
-        return match &entry.value {
-            CachedPattern::Valid(pat) => Ok(pat.clone()),
-            CachedPattern::Invalid { message, fallback } => {
-                if should_throw {
-                    Err(FormatterError::InvalidPattern(message.clone()))
-                } else {
-                    Ok(fallback.clone())
-                }
-            }
-        };
-    }
-
-    match parse_pattern(pattern) {
-        Ok(parsed) => {
-            let arc = Arc::new(parsed);
-            cache.insert(
-                pattern.to_string(),
-                CacheEntry {
-                    value: CachedPattern::Valid(arc.clone()),
-                },
-            );
-            Ok(arc)
-        }
-        Err(err) => {
-            let message = err.to_string();
-            let fallback = build_error_pattern(pattern, &message);
-            cache.insert(
-                pattern.to_string(),
-                CacheEntry {
-                    value: CachedPattern::Invalid {
-                        message: message.clone(),
-                        fallback: fallback.clone(),
-                    },
-                },
-            );
-            if should_throw {
-                Err(FormatterError::Parse(err))
-            } else {
-                Ok(fallback)
-            }
-        }
-    }
-}
-
 fn resolve_locale_tag<'a>(pattern: &'a Pattern, opts: &'a FormatterOptions) -> Option<&'a str> {
     pattern.locale.as_deref().or({
         if opts.locale.is_empty() {
@@ -142,7 +111,11 @@ fn resolve_locale_tag<'a>(pattern: &'a Pattern, opts: &'a FormatterOptions) -> O
 }
 
 fn get_part(value: f64, parts: &[Section]) -> Option<&Section> {
-    for part in parts.iter().take(3) {
+    get_part_index(value, parts).map(|idx| &parts[idx])
+}
+
+fn get_part_index(value: f64, parts: &[Section]) -> Option<usize> {
+    for (idx, part) in parts.iter().enumerate().take(3) {
         if let Some(cond) = &part.condition {
             let operand = cond.operand;
             let result = match cond.operator {
@@ -154,10 +127,10 @@ fn get_part(value: f64, parts: &[Section]) -> Option<&Section> {
                 ConditionOperator::NotEqual => value != operand,
             };
             if result {
-                return Some(part);
+                return Some(idx);
             }
         } else {
-            return Some(part);
+            return Some(idx);
         }
     }
     None
@@ -189,9 +162,9 @@ fn resolve_color_from_section(section: &Section, opts: &FormatterOptions) -> Opt
     }
 }
 
-fn locale_for(pattern: &Pattern, opts: &FormatterOptions) -> &'static locale::Locale {
+fn locale_for(pattern: &Pattern, opts: &FormatterOptions) -> Arc<locale::Locale> {
     let tag = resolve_locale_tag(pattern, opts);
-    get_locale_or_default(tag)
+    locale_for_tag(tag, opts)
 }
 
 pub fn format<'a, V>(pattern: &str, value: V) -> Result<String, FormatterError>
@@ -209,13 +182,37 @@ pub fn format_with_options<'a, V>(
 where
     V: Into<FormatValue<'a>>,
 {
-    let parse_data = prepare_pattern(pattern, options.throws)?;
+    let parse_data = prepare_pattern(pattern, &options)?;
     let locale = locale_for(&parse_data, &options);
-    let parts = &parse_data.partitions;
-    let default_text = default_text_section();
-    let text_section = parts.get(3).unwrap_or(default_text.as_ref());
+    let sections = resolve_sections(&parse_data, &options);
+    format_value(&parse_data, &sections, value.into(), &options, &locale)
+}
+
+fn format_value(
+    pattern: &Pattern,
+    sections: &[ResolvedSection],
+    value: FormatValue<'_>,
+    options: &FormatterOptions,
+    locale: &locale::Locale,
+) -> Result<String, FormatterError> {
+    let parts = &pattern.partitions;
+    let run_text = |text: Cow<'_, str>| match parts.get(3) {
+        Some(section) => run_part(
+            run_part::RunValue::Text(text),
+            section,
+            resolved_section(sections, 3),
+            options,
+            locale,
+        ),
+        None => run_part(
+            run_part::RunValue::Text(text),
+            &default_text_section(),
+            resolved_section(sections, 3),
+            options,
+            locale,
+        ),
+    };
 
-    let value = value.into();
     match value {
         FormatValue::Null => Ok(String::new()),
         FormatValue::Boolean(flag) => {
@@ -224,39 +221,37 @@ where
             } else {
                 locale.bool_false().to_string()
             };
-            run_part(
-                run_part::RunValue::Text(Cow::Owned(text)),
-                text_section,
-                &options,
-                locale,
-            )
+            run_text(Cow::Owned(text))
         }
-        FormatValue::Text(text) => run_part(
-            run_part::RunValue::Text(text),
-            text_section,
-            &options,
-            locale,
-        ),
-        FormatValue::Number(num) => format_number(num, parts, &options, locale),
-        FormatValue::BigInt(big) => format_bigint(big, parts, &options, locale),
-        FormatValue::Date(date) => {
-            if let Some(serial) = date_to_serial(&date, options.ignore_timezone) {
-                format_number(serial, parts, &options, locale)
-            } else {
-                run_part(
-                    run_part::RunValue::Text(Cow::Owned("".to_string())),
-                    text_section,
-                    &options,
+        FormatValue::Text(text) => run_text(text),
+        FormatValue::Number(num) => format_number(num, parts, sections, options, locale),
+        FormatValue::BigInt(big) => format_bigint(big, parts, sections, options, locale),
+        FormatValue::Duration(span) => {
+            let days = span.as_days();
+            match get_part_index(days, parts) {
+                Some(idx) => run_part(
+                    run_part::RunValue::Duration(span),
+                    &parts[idx],
+                    resolved_section(sections, idx),
+                    options,
                     locale,
-                )
+                ),
+                None => Ok(options.overflow.clone()),
             }
         }
+        FormatValue::Date(date) => match date_to_serial(&date, options) {
+            Ok(serial) => format_number(serial, parts, sections, options, locale),
+            Err(err) if options.date_error_throws => Err(err),
+            Err(FormatterError::UnknownTimeZone(_)) => Ok(options.invalid.clone()),
+            Err(_) => Ok(options.overflow.clone()),
+        },
     }
 }
 
 fn format_number(
     value: f64,
     parts: &[Section],
+    sections: &[ResolvedSection],
     options: &FormatterOptions,
     locale: &locale::Locale,
 ) -> Result<String, FormatterError> {
@@ -272,26 +267,35 @@ fn format_number(
         return Ok(result);
     }
 
-    let part = get_part(value, parts);
-    if let Some(section) = part {
-        run_part(run_part::RunValue::Number(value), section, options, locale)
-    } else {
-        Ok(options.overflow.clone())
+    match get_part_index(value, parts) {
+        Some(idx) => run_part(
+            run_part::RunValue::Number(value),
+            &parts[idx],
+            resolved_section(sections, idx),
+            options,
+            locale,
+        ),
+        None => Ok(options.overflow.clone()),
     }
 }
 
 fn format_bigint(
     value: num_bigint::BigInt,
     parts: &[Section],
+    sections: &[ResolvedSection],
     options: &FormatterOptions,
     locale: &locale::Locale,
 ) -> Result<String, FormatterError> {
     let condition_value = bigint_condition_value(&value);
-    let part = get_part(condition_value, parts);
-    if let Some(section) = part {
-        run_part(run_part::RunValue::BigInt(&value), section, options, locale)
-    } else {
-        Ok(options.overflow.clone())
+    match get_part_index(condition_value, parts) {
+        Some(idx) => run_part(
+            run_part::RunValue::BigInt(&value),
+            &parts[idx],
+            resolved_section(sections, idx),
+            options,
+            locale,
+        ),
+        None => Ok(options.overflow.clone()),
     }
 }
 
@@ -303,13 +307,20 @@ pub fn format_color<'a, V>(
 where
     V: Into<FormatValue<'a>>,
 {
-    let value = value.into();
-    let parse_data = prepare_pattern(pattern, options.throws)?;
-    let parts = &parse_data.partitions;
+    let parse_data = prepare_pattern(pattern, &options)?;
+    Ok(color_value(&parse_data, &value.into(), &options))
+}
+
+fn color_value(
+    pattern: &Pattern,
+    value: &FormatValue<'_>,
+    options: &FormatterOptions,
+) -> Option<ColorValue> {
+    let parts = &pattern.partitions;
     let default_text = default_text_section();
     let mut part: Option<&Section> = parts.get(3).or_else(|| Some(default_text.as_ref()));
 
-    match &value {
+    match value {
         FormatValue::Number(num) if num.is_finite() => {
             part = get_part(*num, parts);
         }
@@ -317,13 +328,11 @@ where
             let num = bigint_condition_value(big);
             part = get_part(num, parts);
         }
+        FormatValue::Duration(span) => {
+            part = get_part(span.as_days(), parts);
+        }
         _ => {}
     }
 
-    let section = match part {
-        Some(section) => section,
-        None => return Ok(None),
-    };
-
-    Ok(resolve_color_from_section(section, &options))
+    resolve_color_from_section(part?, options)
 }
@@ -0,0 +1,843 @@
+//! Numeral systems for `[DBNumN]` and `[NatNumN]` sections, and the
+//! registry that maps those tags to a system.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::parser::model::NumeralTag;
+
+use super::locale::{Locale, resolve_locale};
+
+/// The symbols a numeral system writes numbers with.
+///
+/// Systems with units spell numbers out (一千二百三十四); the others write
+/// their own digits in place of the ASCII ones.
+pub trait NumeralSystem: Send + Sync {
+    /// The symbols for 0 to 9.
+    fn digits(&self) -> [&str; 10];
+
+    /// The words for ten, a hundred and a thousand, in systems that spell
+    /// numbers out.
+    fn units(&self) -> Option<[&str; 3]> {
+        None
+    }
+
+    /// The word for 10^(4 × `group`), such as 万 for 1 and 亿 for 2.
+    fn group_word(&self, _group: usize) -> Option<&str> {
+        None
+    }
+
+    fn negative(&self) -> &str {
+        "-"
+    }
+
+    fn decimal(&self) -> &str {
+        "."
+    }
+
+    /// Whether the system writes each number of the formatted text whole,
+    /// through `format_text`, rather than swapping its digits.
+    fn spells_out(&self) -> bool {
+        self.units().is_some()
+    }
+
+    /// Whether spelled-out numbers drop the "one" before units: 百 rather
+    /// than 一百.
+    fn drops_leading_one(&self) -> bool {
+        false
+    }
+
+    /// Whether spelled-out numbers mark skipped places with a zero: 一百〇一.
+    fn marks_zeros(&self) -> bool {
+        true
+    }
+
+    /// Writes a value the way a `General` section shows it.
+    fn format(&self, number: f64) -> String {
+        spell_out(self, number, leading_one(self))
+    }
+
+    /// Writes the year of a date, digit by digit.
+    fn format_year(&self, year: i32) -> String {
+        write_digits(self, &year.to_string())
+    }
+
+    /// Writes a month, day or time field of a date.
+    fn format_date_field(&self, value: i32) -> String {
+        self.format(f64::from(value))
+    }
+
+    /// Writes a number a section formatted, given in ASCII digits with an
+    /// optional `-` and `.`, keeping its decimal places.
+    fn format_text(&self, text: &str) -> String {
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, text),
+        };
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        let mut result = String::new();
+        if negative {
+            result.push_str(self.negative());
+        }
+        result.push_str(&self.format(integer.parse().unwrap_or(0.0)));
+        if !fraction.is_empty() {
+            result.push_str(self.decimal());
+            result.push_str(&write_digits(self, fraction));
+        }
+        result
+    }
+}
+
+impl fmt::Debug for dyn NumeralSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("NumeralSystem")
+    }
+}
+
+/// When a spelled-out number writes the "one" before a unit.
+#[derive(Debug, Clone, Copy)]
+enum LeadingOne {
+    /// 一十二, 一百.
+    Always,
+    /// 十二 but 一百.
+    NotBeforeTen,
+    /// 十二, 百.
+    Never,
+}
+
+/// The numeral systems that come with the crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BuiltinNumerals {
+    /// 一千二百三十四, the `[DBNum1]` system.
+    ChineseSimplified,
+    /// 壹仟贰佰叁拾肆, the `[DBNum2]` system.
+    ChineseSimplifiedFormal,
+    /// 一千二百三十四, with 萬 and 億 for larger groups.
+    ChineseTraditional,
+    /// 壹仟貳佰參拾肆.
+    ChineseTraditionalFormal,
+    /// 一二三四, digit by digit, the `[DBNum3]` system.
+    ChineseDigits,
+    /// １２３４, the `[DBNum4]` system.
+    FullWidth,
+    /// 千二百三十四.
+    Japanese,
+    /// 壱阡弐百参拾四.
+    JapaneseFormal,
+    /// 천이백삼십사.
+    KoreanHangul,
+    /// 千二百三十四, with 萬 for ten thousand.
+    KoreanHanja,
+    /// ١٢٣٤
+    ArabicIndic,
+    /// १२३४
+    Devanagari,
+    /// ๑๒๓๔
+    Thai,
+    /// MCCXXXIV, for whole numbers from 1 to 3999.
+    Roman,
+}
+
+const CHINESE_DIGITS: [&str; 10] = ["〇", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+const CHINESE_UNITS: [&str; 3] = ["十", "百", "千"];
+const SIMPLIFIED_FORMAL_DIGITS: [&str; 10] =
+    ["零", "壹", "贰", "叁", "肆", "伍", "陆", "柒", "捌", "玖"];
+const TRADITIONAL_FORMAL_DIGITS: [&str; 10] =
+    ["零", "壹", "貳", "參", "肆", "伍", "陸", "柒", "捌", "玖"];
+const FORMAL_UNITS: [&str; 3] = ["拾", "佰", "仟"];
+const SIMPLIFIED_GROUPS: [&str; 4] = ["万", "亿", "兆", "京"];
+const TRADITIONAL_GROUPS: [&str; 4] = ["萬", "億", "兆", "京"];
+const JAPANESE_GROUPS: [&str; 4] = ["万", "億", "兆", "京"];
+const JAPANESE_FORMAL_DIGITS: [&str; 10] =
+    ["〇", "壱", "弐", "参", "四", "伍", "六", "七", "八", "九"];
+const JAPANESE_FORMAL_UNITS: [&str; 3] = ["拾", "百", "阡"];
+const HANGUL_DIGITS: [&str; 10] = ["영", "일", "이", "삼", "사", "오", "육", "칠", "팔", "구"];
+const HANGUL_UNITS: [&str; 3] = ["십", "백", "천"];
+const HANGUL_GROUPS: [&str; 4] = ["만", "억", "조", "경"];
+const HANJA_DIGITS: [&str; 10] = ["零", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+const FULL_WIDTH_DIGITS: [&str; 10] = ["０", "１", "２", "３", "４", "５", "６", "７", "８", "９"];
+const ARABIC_INDIC_DIGITS: [&str; 10] = ["٠", "١", "٢", "٣", "٤", "٥", "٦", "٧", "٨", "٩"];
+const DEVANAGARI_DIGITS: [&str; 10] = ["०", "१", "२", "३", "४", "५", "६", "७", "८", "९"];
+const THAI_DIGITS: [&str; 10] = ["๐", "๑", "๒", "๓", "๔", "๕", "๖", "๗", "๘", "๙"];
+const ASCII_DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+const ROMAN: [(u32, &str); 13] = [
+    (1000, "M"),
+    (900, "CM"),
+    (500, "D"),
+    (400, "CD"),
+    (100, "C"),
+    (90, "XC"),
+    (50, "L"),
+    (40, "XL"),
+    (10, "X"),
+    (9, "IX"),
+    (5, "V"),
+    (4, "IV"),
+    (1, "I"),
+];
+
+impl NumeralSystem for BuiltinNumerals {
+    fn digits(&self) -> [&str; 10] {
+        match self {
+            BuiltinNumerals::ChineseSimplified
+            | BuiltinNumerals::ChineseTraditional
+            | BuiltinNumerals::ChineseDigits
+            | BuiltinNumerals::Japanese => CHINESE_DIGITS,
+            BuiltinNumerals::ChineseSimplifiedFormal => SIMPLIFIED_FORMAL_DIGITS,
+            BuiltinNumerals::ChineseTraditionalFormal => TRADITIONAL_FORMAL_DIGITS,
+            BuiltinNumerals::JapaneseFormal => JAPANESE_FORMAL_DIGITS,
+            BuiltinNumerals::KoreanHangul => HANGUL_DIGITS,
+            BuiltinNumerals::KoreanHanja => HANJA_DIGITS,
+            BuiltinNumerals::FullWidth => FULL_WIDTH_DIGITS,
+            BuiltinNumerals::ArabicIndic => ARABIC_INDIC_DIGITS,
+            BuiltinNumerals::Devanagari => DEVANAGARI_DIGITS,
+            BuiltinNumerals::Thai => THAI_DIGITS,
+            BuiltinNumerals::Roman => ASCII_DIGITS,
+        }
+    }
+
+    fn units(&self) -> Option<[&str; 3]> {
+        match self {
+            BuiltinNumerals::ChineseSimplified
+            | BuiltinNumerals::ChineseTraditional
+            | BuiltinNumerals::Japanese
+            | BuiltinNumerals::KoreanHanja => Some(CHINESE_UNITS),
+            BuiltinNumerals::ChineseSimplifiedFormal
+            | BuiltinNumerals::ChineseTraditionalFormal => Some(FORMAL_UNITS),
+            BuiltinNumerals::JapaneseFormal => Some(JAPANESE_FORMAL_UNITS),
+            BuiltinNumerals::KoreanHangul => Some(HANGUL_UNITS),
+            _ => None,
+        }
+    }
+
+    fn group_word(&self, group: usize) -> Option<&str> {
+        let words = match self {
+            BuiltinNumerals::ChineseSimplified | BuiltinNumerals::ChineseSimplifiedFormal => {
+                SIMPLIFIED_GROUPS
+            }
+            BuiltinNumerals::ChineseTraditional
+            | BuiltinNumerals::ChineseTraditionalFormal
+            | BuiltinNumerals::JapaneseFormal
+            | BuiltinNumerals::KoreanHanja => TRADITIONAL_GROUPS,
+            BuiltinNumerals::Japanese => JAPANESE_GROUPS,
+            BuiltinNumerals::KoreanHangul => HANGUL_GROUPS,
+            _ => return None,
+        };
+        words.get(group.checked_sub(1)?).copied()
+    }
+
+    fn negative(&self) -> &str {
+        match self {
+            BuiltinNumerals::ChineseSimplified
+            | BuiltinNumerals::ChineseSimplifiedFormal
+            | BuiltinNumerals::ChineseDigits => "负",
+            BuiltinNumerals::ChineseTraditional | BuiltinNumerals::ChineseTraditionalFormal => "負",
+            BuiltinNumerals::FullWidth => "－",
+            _ => "-",
+        }
+    }
+
+    fn decimal(&self) -> &str {
+        match self {
+            BuiltinNumerals::FullWidth => "．",
+            BuiltinNumerals::ArabicIndic => "٫",
+            _ => ".",
+        }
+    }
+
+    fn spells_out(&self) -> bool {
+        matches!(self, BuiltinNumerals::Roman) || self.units().is_some()
+    }
+
+    fn drops_leading_one(&self) -> bool {
+        matches!(
+            self,
+            BuiltinNumerals::Japanese
+                | BuiltinNumerals::KoreanHangul
+                | BuiltinNumerals::KoreanHanja
+        )
+    }
+
+    fn marks_zeros(&self) -> bool {
+        !matches!(
+            self,
+            BuiltinNumerals::Japanese
+                | BuiltinNumerals::JapaneseFormal
+                | BuiltinNumerals::KoreanHangul
+                | BuiltinNumerals::KoreanHanja
+        )
+    }
+
+    fn format(&self, number: f64) -> String {
+        match self {
+            BuiltinNumerals::Roman => roman(number).unwrap_or_else(|| number.to_string()),
+            _ => spell_out(self, number, leading_one(self)),
+        }
+    }
+
+    fn format_year(&self, year: i32) -> String {
+        match self {
+            BuiltinNumerals::Roman => self.format(f64::from(year)),
+            _ => write_digits(self, &year.to_string()),
+        }
+    }
+
+    fn format_date_field(&self, value: i32) -> String {
+        let value = f64::from(value);
+        match self {
+            BuiltinNumerals::ChineseSimplified | BuiltinNumerals::ChineseTraditional => {
+                spell_out(self, value, LeadingOne::NotBeforeTen)
+            }
+            _ => self.format(value),
+        }
+    }
+}
+
+fn leading_one<S: NumeralSystem + ?Sized>(system: &S) -> LeadingOne {
+    if system.drops_leading_one() {
+        LeadingOne::Never
+    } else {
+        LeadingOne::Always
+    }
+}
+
+/// Spells `number` out with the system's units and group words, falling
+/// back to digits for systems without them and numbers beyond them.
+fn spell_out<S: NumeralSystem + ?Sized>(system: &S, number: f64, ones: LeadingOne) -> String {
+    let Some(units) = system.units() else {
+        return write_digits(system, &number.to_string());
+    };
+    if !number.is_finite() || number.abs() > 9_999_999_999_999_999_999.0 {
+        return number.to_string();
+    }
+    let digits = system.digits();
+
+    let mut result = String::new();
+    if number.is_sign_negative() {
+        result.push_str(system.negative());
+    }
+
+    let abs = number.abs();
+    match spell_integer(system, abs.trunc() as u64, &units, ones) {
+        Some(integer) => result.push_str(&integer),
+        None => return write_digits(system, &number.to_string()),
+    }
+
+    if abs.fract() > 1e-9 {
+        result.push_str(system.decimal());
+        let text = number.to_string();
+        if let Some((_, fraction)) = text.split_once('.') {
+            for ch in fraction.chars() {
+                if let Some(d) = ch.to_digit(10) {
+                    result.push_str(digits[d as usize]);
+                }
+            }
+        }
+    }
+
+    result
+}
+
+fn spell_integer<S: NumeralSystem + ?Sized>(
+    system: &S,
+    mut n: u64,
+    units: &[&str; 3],
+    ones: LeadingOne,
+) -> Option<String> {
+    let digits = system.digits();
+    let zeros = system.marks_zeros();
+    if n == 0 {
+        return Some(digits[0].to_string());
+    }
+
+    let mut result = String::new();
+    let mut group = 0;
+    let mut needs_zero = false;
+
+    while n > 0 {
+        let part = n % 10000;
+        if needs_zero {
+            result.insert_str(0, digits[0]);
+        }
+
+        if part > 0 {
+            let mut part_str = spell_four_digits(part, &digits, units, ones, zeros);
+            if group > 0 {
+                part_str.push_str(system.group_word(group)?);
+            }
+            result.insert_str(0, &part_str);
+            needs_zero = zeros && part < 1000 && n / 10000 > 0;
+        } else {
+            needs_zero = zeros && !result.is_empty() && !result.starts_with(digits[0]);
+        }
+
+        n /= 10000;
+        group += 1;
+    }
+
+    Some(result)
+}
+
+fn spell_four_digits(
+    mut n: u64,
+    digits: &[&str; 10],
+    units: &[&str; 3],
+    ones: LeadingOne,
+    zeros: bool,
+) -> String {
+    let mut result = String::new();
+    let mut was_zero = true;
+
+    for i in (0..4).rev() {
+        let base = 10u64.pow(i);
+        let d = n / base;
+
+        if d > 0 {
+            let skip_one = d == 1
+                && match ones {
+                    LeadingOne::Always => false,
+                    LeadingOne::NotBeforeTen => i == 1,
+                    LeadingOne::Never => i > 0,
+                };
+            if !skip_one {
+                result.push_str(digits[d as usize]);
+            }
+            if i > 0 {
+                result.push_str(units[i as usize - 1]);
+            }
+            was_zero = false;
+        } else {
+            if zeros && !was_zero && !result.ends_with(digits[0]) {
+                result.push_str(digits[0]);
+            }
+            was_zero = true;
+        }
+        n %= base;
+    }
+
+    while let Some(trimmed) = result.strip_suffix(digits[0]) {
+        result.truncate(trimmed.len());
+    }
+
+    result
+}
+
+/// Writes the characters of a plain number in the system's digits and
+/// markers.
+fn write_digits<S: NumeralSystem + ?Sized>(system: &S, text: &str) -> String {
+    let digits = system.digits();
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '0'..='9' => result.push_str(digits[c as usize - '0' as usize]),
+            '-' => result.push_str(system.negative()),
+            '.' => result.push_str(system.decimal()),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+/// Swaps the ASCII digits of formatted text for the system's digits.
+pub(super) fn transliterate(system: &dyn NumeralSystem, text: &str) -> String {
+    let digits = system.digits();
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            result.push_str(digits[c as usize - '0' as usize]);
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Spells out the numbers in formatted text: runs of ASCII digits joined
+/// by the locale's group and decimal separators, with the minus sign
+/// right before them. Bytes inside `verbatim` are left as they are.
+pub(super) fn spell_runs(
+    system: &dyn NumeralSystem,
+    text: &str,
+    verbatim: &[Range<usize>],
+    locale: &Locale,
+) -> String {
+    let literal = |at: usize| verbatim.iter().any(|range| range.contains(&at));
+    let digit_at =
+        |at: usize| !literal(at) && text.as_bytes().get(at).is_some_and(|b| b.is_ascii_digit());
+    let separator_at = |at: usize, separator: &str| {
+        !separator.is_empty()
+            && text[at..].starts_with(separator)
+            && !literal(at)
+            && digit_at(at + separator.len())
+    };
+
+    let mut result = String::with_capacity(text.len());
+    let mut pos = 0;
+    while let Some(ch) = text[pos..].chars().next() {
+        if !digit_at(pos) {
+            result.push(ch);
+            pos += ch.len_utf8();
+            continue;
+        }
+        let mut number = String::new();
+        let sign = &locale.negative;
+        if !sign.is_empty() && text[..pos].ends_with(sign.as_str()) && !literal(pos - sign.len()) {
+            result.truncate(result.len() - sign.len());
+            number.push('-');
+        }
+        let mut point = false;
+        loop {
+            if digit_at(pos) {
+                number.push(char::from(text.as_bytes()[pos]));
+                pos += 1;
+            } else if !point && separator_at(pos, &locale.decimal) {
+                number.push('.');
+                pos += locale.decimal.len();
+                point = true;
+            } else if !point && separator_at(pos, &locale.group) {
+                pos += locale.group.len();
+            } else {
+                break;
+            }
+        }
+        result.push_str(&system.format_text(&number));
+    }
+    result
+}
+
+fn roman(number: f64) -> Option<String> {
+    if number.fract() != 0.0 || !(1.0..=3999.0).contains(&number) {
+        return None;
+    }
+    let mut n = number as u32;
+    let mut result = String::new();
+    for (value, letters) in ROMAN {
+        while n >= value {
+            result.push_str(letters);
+            n -= value;
+        }
+    }
+    Some(result)
+}
+
+/// A built-in system written another way, for the `[NatNumN]` forms that
+/// mix one system's words with other digits or rules.
+struct Variant(BuiltinNumerals, Form);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Form {
+    /// The system's digits, one per place: 一二三.
+    Characters,
+    /// Spelled out with full-width digits: １百２十３.
+    FullWidthText,
+    /// Spelled out with the "one" before every unit: 一百二十三.
+    Long,
+    /// Spelled out without the "one" before units: 百二十三.
+    Short,
+}
+
+impl NumeralSystem for Variant {
+    fn digits(&self) -> [&str; 10] {
+        match self.1 {
+            Form::FullWidthText => FULL_WIDTH_DIGITS,
+            _ => self.0.digits(),
+        }
+    }
+
+    fn units(&self) -> Option<[&str; 3]> {
+        match self.1 {
+            Form::Characters => None,
+            _ => self.0.units(),
+        }
+    }
+
+    fn group_word(&self, group: usize) -> Option<&str> {
+        self.0.group_word(group)
+    }
+
+    fn negative(&self) -> &str {
+        self.0.negative()
+    }
+
+    fn decimal(&self) -> &str {
+        self.0.decimal()
+    }
+
+    fn drops_leading_one(&self) -> bool {
+        self.1 == Form::Short
+    }
+
+    fn marks_zeros(&self) -> bool {
+        self.0.marks_zeros()
+    }
+}
+
+const ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+const SCALES: [&str; 13] = [
+    "",
+    "thousand",
+    "million",
+    "billion",
+    "trillion",
+    "quadrillion",
+    "quintillion",
+    "sextillion",
+    "septillion",
+    "octillion",
+    "nonillion",
+    "decillion",
+    "undecillion",
+];
+
+/// Numbers in English words, the `[NatNum12]` form of English locales.
+struct EnglishWords;
+
+impl NumeralSystem for EnglishWords {
+    fn digits(&self) -> [&str; 10] {
+        ASCII_DIGITS
+    }
+
+    fn negative(&self) -> &str {
+        "minus"
+    }
+
+    fn decimal(&self) -> &str {
+        "point"
+    }
+
+    fn spells_out(&self) -> bool {
+        true
+    }
+
+    fn format(&self, number: f64) -> String {
+        if !number.is_finite() || number.abs() >= u128::MAX as f64 {
+            return number.to_string();
+        }
+        let text = number.abs().to_string();
+        let fraction = text.split_once('.').map_or("", |(_, fraction)| fraction);
+        self.words(
+            number.is_sign_negative() && number != 0.0,
+            number.abs().trunc() as u128,
+            fraction,
+        )
+    }
+
+    fn format_text(&self, text: &str) -> String {
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, text),
+        };
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        match integer.parse() {
+            Ok(integer) => self.words(negative, integer, fraction),
+            Err(_) => text.to_string(),
+        }
+    }
+
+    fn format_year(&self, year: i32) -> String {
+        self.format(f64::from(year))
+    }
+}
+
+impl EnglishWords {
+    /// The words for a number, with the decimals read digit by digit.
+    fn words(&self, negative: bool, integer: u128, fraction: &str) -> String {
+        let mut words = Vec::new();
+        if negative {
+            words.push(self.negative().to_string());
+        }
+        words.push(english_integer(integer));
+        if !fraction.is_empty() {
+            words.push(self.decimal().to_string());
+            words.extend(
+                fraction
+                    .chars()
+                    .filter_map(|c| c.to_digit(10))
+                    .map(|d| ONES[d as usize].to_string()),
+            );
+        }
+        words.join(" ")
+    }
+}
+
+/// The words for a whole number. `SCALES` names every group of three
+/// digits a `u128` has; larger values, from about 3.4e38 up, are left in
+/// digits by the callers.
+fn english_integer(mut n: u128) -> String {
+    if n == 0 {
+        return ONES[0].to_string();
+    }
+    let mut groups = Vec::new();
+    let mut scale = 0;
+    while n > 0 {
+        let part = (n % 1000) as usize;
+        if part > 0 {
+            let mut words = english_hundreds(part);
+            if scale > 0 {
+                words.push(' ');
+                words.push_str(SCALES[scale]);
+            }
+            groups.push(words);
+        }
+        n /= 1000;
+        scale += 1;
+    }
+    groups.reverse();
+    groups.join(" ")
+}
+
+fn english_hundreds(n: usize) -> String {
+    let mut words = Vec::new();
+    if n >= 100 {
+        words.push(format!("{} hundred", ONES[n / 100]));
+    }
+    match n % 100 {
+        0 => {}
+        rest @ 1..20 => words.push(ONES[rest].to_string()),
+        rest if rest % 10 == 0 => words.push(TENS[rest / 10].to_string()),
+        rest => words.push(format!("{}-{}", TENS[rest / 10], ONES[rest % 10])),
+    }
+    words.join(" ")
+}
+
+/// The system `[NatNumN]` picks in a locale, after LibreOffice's table:
+/// native digits, CJK characters and text in lower, formal, full-width,
+/// long and short forms, Hangul for Korean, and words for NatNum12.
+fn natnum_system(n: u8, locale: Option<&str>) -> Option<Arc<dyn NumeralSystem>> {
+    use BuiltinNumerals::*;
+
+    let resolved = locale.and_then(resolve_locale);
+    let (language, region) = match resolved.as_deref() {
+        Some(tag) => tag.split_once('_').unwrap_or((tag, "")),
+        None => ("en", ""),
+    };
+    let system: Arc<dyn NumeralSystem> = match (language, n) {
+        ("zh", _) => {
+            let traditional = matches!(region, "TW" | "HK" | "MO");
+            let (lower, formal) = if traditional {
+                (ChineseTraditional, ChineseTraditionalFormal)
+            } else {
+                (ChineseSimplified, ChineseSimplifiedFormal)
+            };
+            match n {
+                1 => Arc::new(Variant(lower, Form::Characters)),
+                2 => Arc::new(Variant(formal, Form::Characters)),
+                3 => Arc::new(FullWidth),
+                4 | 12 => Arc::new(lower),
+                5 => Arc::new(formal),
+                6 => Arc::new(Variant(lower, Form::FullWidthText)),
+                _ => return None,
+            }
+        }
+        ("ja", _) => match n {
+            1 => Arc::new(Variant(Japanese, Form::Characters)),
+            2 => Arc::new(Variant(JapaneseFormal, Form::Characters)),
+            3 => Arc::new(FullWidth),
+            4 => Arc::new(Variant(Japanese, Form::Long)),
+            5 => Arc::new(JapaneseFormal),
+            6 => Arc::new(Variant(Japanese, Form::FullWidthText)),
+            7 | 12 => Arc::new(Japanese),
+            8 => Arc::new(Variant(JapaneseFormal, Form::Short)),
+            _ => return None,
+        },
+        ("ko", _) => match n {
+            1 => Arc::new(Variant(KoreanHanja, Form::Characters)),
+            2 => Arc::new(Variant(ChineseTraditionalFormal, Form::Characters)),
+            3 => Arc::new(FullWidth),
+            4 => Arc::new(Variant(KoreanHanja, Form::Long)),
+            5 => Arc::new(ChineseTraditionalFormal),
+            6 => Arc::new(Variant(KoreanHanja, Form::FullWidthText)),
+            7 => Arc::new(KoreanHanja),
+            8 => Arc::new(Variant(ChineseTraditionalFormal, Form::Short)),
+            9 => Arc::new(Variant(KoreanHangul, Form::Characters)),
+            10 => Arc::new(Variant(KoreanHangul, Form::Long)),
+            11 | 12 => Arc::new(KoreanHangul),
+            _ => return None,
+        },
+        // The Maghreb writes Arabic with European digits.
+        ("ar", 1) if !matches!(region, "MA" | "DZ" | "TN" | "LY") => Arc::new(ArabicIndic),
+        ("hi" | "mr" | "ne" | "sa", 1) => Arc::new(Devanagari),
+        ("th", 1) => Arc::new(Thai),
+        ("en", 12) => Arc::new(EnglishWords),
+        _ => return None,
+    };
+    Some(system)
+}
+
+static NUMERAL_SYSTEMS: OnceLock<RwLock<HashMap<NumeralTag, Arc<dyn NumeralSystem>>>> =
+    OnceLock::new();
+
+fn numeral_systems() -> &'static RwLock<HashMap<NumeralTag, Arc<dyn NumeralSystem>>> {
+    NUMERAL_SYSTEMS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Registers `system` for a `[DBNumN]` or `[NatNumN]` tag, replacing the
+/// system the tag had, built-in or not.
+pub fn add_numeral_system(tag: NumeralTag, system: impl NumeralSystem + 'static) {
+    numeral_systems()
+        .write()
+        .expect("numeral system registry poisoned")
+        .insert(tag, Arc::new(system));
+}
+
+/// Drops the system registered for `tag`. Tags with a built-in system go
+/// back to it.
+pub fn remove_numeral_system(tag: NumeralTag) -> bool {
+    numeral_systems()
+        .write()
+        .expect("numeral system registry poisoned")
+        .remove(&tag)
+        .is_some()
+}
+
+/// The system a tag writes numbers in, for the section's locale tag. Tags
+/// without one leave numbers as the rest of the section formats them.
+/// Sections look this up once, when their pattern is resolved.
+pub(super) fn numeral_system(
+    tag: NumeralTag,
+    locale: Option<&str>,
+) -> Option<Arc<dyn NumeralSystem>> {
+    if let Some(system) = numeral_systems()
+        .read()
+        .expect("numeral system registry poisoned")
+        .get(&tag)
+    {
+        return Some(system.clone());
+    }
+    let builtin = match tag {
+        NumeralTag::DbNum(1) => BuiltinNumerals::ChineseSimplified,
+        NumeralTag::DbNum(2) => BuiltinNumerals::ChineseSimplifiedFormal,
+        NumeralTag::DbNum(3) => BuiltinNumerals::ChineseDigits,
+        NumeralTag::DbNum(4) => BuiltinNumerals::FullWidth,
+        NumeralTag::NatNum(n) => return natnum_system(n, locale),
+        _ => return None,
+    };
+    Some(Arc::new(builtin))
+}
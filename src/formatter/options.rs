@@ -1,3 +1,12 @@
+use std::sync::Arc;
+
+use crate::parser::model::CalendarKind;
+
+use super::calendar::Calendar;
+use super::era::EraTable;
+use super::locale::{Locale, LocaleRegistry};
+use super::timezone::TimeZone;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct FormatterOptions {
     pub overflow: String,
@@ -6,15 +15,40 @@ pub struct FormatterOptions {
     pub bigint_error_number: bool,
     pub date_span_large: bool,
     pub leap_1900: bool,
+    /// Count serials from 1904-01-01 as Mac workbooks do, instead of from
+    /// 1900-01-00.
+    pub date_1904: bool,
     pub nbsp: bool,
     pub throws: bool,
     pub invalid: String,
     pub locale: String,
+    /// Render dates with a zone as wall-clock time in their own zone, even
+    /// when `time_zone` asks for another.
     pub ignore_timezone: bool,
+    /// The zone to render dates with a zone in.
+    pub time_zone: Option<TimeZone>,
     pub grouping: Vec<u8>,
     pub index_colors: bool,
     pub skip_char: Option<String>,
     pub fill_char: Option<String>,
+    /// Keep parsed patterns in the shared pattern cache. Turn off for
+    /// one-off codes that would only push useful entries out.
+    pub cache_patterns: bool,
+    /// Resolve locale tags against this registry instead of the shared one.
+    pub locale_registry: Option<Arc<LocaleRegistry>>,
+    /// Use this locale whatever the options or the pattern ask for.
+    pub inline_locale: Option<Arc<Locale>>,
+    /// The eras `g` and `e` count in. Without one, Japanese locales use
+    /// the imperial eras, Taiwan uses Minguo years and others have none.
+    pub era_table: Option<Arc<EraTable>>,
+    /// The calendar for date sections whose code leaves them Gregorian.
+    pub calendar: Option<CalendarKind>,
+    /// A calendar for date sections whose code leaves them Gregorian,
+    /// used in place of `calendar` without registering a calendar code.
+    pub custom_calendar: Option<Arc<dyn Calendar>>,
+    /// The year typed dates without one, such as `"3/4"`, fall in. Without
+    /// it such text is not read as a date.
+    pub reference_year: Option<i32>,
 }
 
 impl Default for FormatterOptions {
@@ -26,15 +60,24 @@ impl Default for FormatterOptions {
             bigint_error_number: false,
             date_span_large: true,
             leap_1900: true,
+            date_1904: false,
             nbsp: false,
             throws: true,
             invalid: "######".to_string(),
             locale: String::new(),
             ignore_timezone: false,
+            time_zone: None,
             grouping: vec![3, 3],
             index_colors: true,
             skip_char: None,
             fill_char: None,
+            cache_patterns: true,
+            locale_registry: None,
+            inline_locale: None,
+            era_table: None,
+            calendar: None,
+            custom_calendar: None,
+            reference_year: None,
         }
     }
 }
@@ -67,4 +110,49 @@ impl FormatterOptions {
         self.fill_char = ch;
         self
     }
+
+    pub fn with_cache_patterns(mut self, cache: bool) -> Self {
+        self.cache_patterns = cache;
+        self
+    }
+
+    pub fn with_locale_registry(mut self, registry: Arc<LocaleRegistry>) -> Self {
+        self.locale_registry = Some(registry);
+        self
+    }
+
+    pub fn with_inline_locale(mut self, locale: Arc<Locale>) -> Self {
+        self.inline_locale = Some(locale);
+        self
+    }
+
+    pub fn with_date_1904(mut self, date_1904: bool) -> Self {
+        self.date_1904 = date_1904;
+        self
+    }
+
+    pub fn with_time_zone(mut self, zone: TimeZone) -> Self {
+        self.time_zone = Some(zone);
+        self
+    }
+
+    pub fn with_era_table(mut self, table: EraTable) -> Self {
+        self.era_table = Some(Arc::new(table));
+        self
+    }
+
+    pub fn with_calendar(mut self, calendar: CalendarKind) -> Self {
+        self.calendar = Some(calendar);
+        self
+    }
+
+    pub fn with_custom_calendar(mut self, calendar: impl Calendar + 'static) -> Self {
+        self.custom_calendar = Some(Arc::new(calendar));
+        self
+    }
+
+    pub fn with_reference_year(mut self, year: i32) -> Self {
+        self.reference_year = Some(year);
+        self
+    }
 }
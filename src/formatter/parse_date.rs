@@ -0,0 +1,300 @@
+//! Parsing of typed-in dates and datetimes such as `"3/4/2024"`,
+//! `"4 March 2024"` or `"2024-03-04 13:05"` into serial values.
+
+use super::{
+    locale::{Locale, locale_for_tag},
+    options::FormatterOptions,
+    parse_time::scan_time,
+    serial::{date_to_serial, days_in_month},
+    value::DateValue,
+};
+
+/// Parses a typed-in date or datetime the way spreadsheet cell entry does.
+///
+/// Numeric dates such as `"3/4/2024"` are read month-first when the locale
+/// selected by `options.locale` prefers MDY and day-first otherwise; the
+/// other order is only tried when the preferred one is not a valid date.
+/// Month names come from the locale's `mmmm` and `mmm` tables. Dates without
+/// a year fall in `options.reference_year`, and are not dates without one.
+/// Two-digit years below 30 belong to the 2000s.
+///
+/// Returns the date, its serial value and a format code describing how the
+/// text was written (e.g. `"d mmmm yyyy"`), or `None` when the text is not a
+/// date.
+pub fn parse_date(text: &str, options: &FormatterOptions) -> Option<(DateValue, f64, String)> {
+    let locale = &locale_for_tag(Some(options.locale.as_str()), options);
+    let text = text.trim();
+    let (date_text, time_text) = split_time(text);
+
+    let (mut date, mut pattern) = scan_date(date_text, locale, options.reference_year)?;
+
+    if let Some(time_text) = time_text {
+        let time = scan_time(time_text, locale).filter(|time| !time.elapsed)?;
+        date = date.with_time(time.hours as u8, time.minutes as u8, time.seconds as u8);
+        if time.subsecond > 0.0 {
+            date = date.with_millisecond(time.millisecond());
+        }
+        pattern.push(' ');
+        pattern.push_str(&time.pattern);
+    }
+
+    // 1900-02-29 only exists in Excel's emulation of the Lotus leap bug.
+    let serial = if !is_phantom_leap_day(&date) {
+        date_to_serial(&date, options).ok()?
+    } else if options.leap_1900 && !options.date_1904 {
+        60.0 + time_fraction(&date)
+    } else {
+        return None;
+    };
+    Some((date, serial, pattern))
+}
+
+/// Splits a trailing clock time (`"13:05"`, `"T13:05:00"`) off the date.
+fn split_time(text: &str) -> (&str, Option<&str>) {
+    let Some(colon) = text.find(':') else {
+        return (text, None);
+    };
+    let head = &text[..colon];
+    match head.rfind(|c: char| c.is_whitespace() || c == 'T') {
+        Some(split) => (
+            head[..split].trim_end(),
+            Some(text[split + 1..].trim_start()),
+        ),
+        None => (text, None),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Item<'a> {
+    Number(&'a str),
+    Month { index: u8, long: bool },
+}
+
+fn scan_date(
+    text: &str,
+    locale: &Locale,
+    reference_year: Option<i32>,
+) -> Option<(DateValue, String)> {
+    let (items, separators) = lex(text, locale)?;
+    let prefer_mdy = locale.prefer_mdy;
+
+    let fields: Vec<Field> = match items.as_slice() {
+        [Item::Number(y), Item::Number(_), Item::Number(_)] if y.len() == 4 => {
+            vec![Field::Year, Field::Month, Field::Day]
+        }
+        [Item::Number(_), Item::Number(_), Item::Number(_)] => {
+            let mdy = vec![Field::Month, Field::Day, Field::Year];
+            let dmy = vec![Field::Day, Field::Month, Field::Year];
+            if prefer_mdy {
+                vec![mdy, dmy]
+            } else {
+                vec![dmy, mdy]
+            }
+            .into_iter()
+            .find(|order| build_date(&items, order, reference_year).is_some())?
+        }
+        [Item::Number(_), Item::Number(y)] if y.len() == 4 => vec![Field::Month, Field::Year],
+        [Item::Number(_), Item::Number(_)] => {
+            let md = vec![Field::Month, Field::Day];
+            let dm = vec![Field::Day, Field::Month];
+            if prefer_mdy {
+                vec![md, dm]
+            } else {
+                vec![dm, md]
+            }
+            .into_iter()
+            .find(|order| build_date(&items, order, reference_year).is_some())?
+        }
+        [Item::Number(_), Item::Month { .. }, Item::Number(_)] => {
+            vec![Field::Day, Field::Month, Field::Year]
+        }
+        [Item::Month { .. }, Item::Number(_), Item::Number(_)] => {
+            vec![Field::Month, Field::Day, Field::Year]
+        }
+        [Item::Number(_), Item::Month { .. }] => vec![Field::Day, Field::Month],
+        [Item::Month { .. }, Item::Number(y)] if y.len() == 4 => vec![Field::Month, Field::Year],
+        [Item::Month { .. }, Item::Number(_)] => vec![Field::Month, Field::Day],
+        _ => return None,
+    };
+
+    let date = build_date(&items, &fields, reference_year)?;
+
+    let mut pattern = String::new();
+    for (idx, (item, field)) in items.iter().zip(&fields).enumerate() {
+        if idx > 0 {
+            pattern.push_str(&separators[idx - 1]);
+        }
+        pattern.push_str(&field_pattern(item, *field));
+    }
+    Some((date, pattern))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Year,
+    Month,
+    Day,
+}
+
+fn build_date(
+    items: &[Item<'_>],
+    fields: &[Field],
+    reference_year: Option<i32>,
+) -> Option<DateValue> {
+    let mut year = None;
+    let mut month = None;
+    let mut day = None;
+    for (item, field) in items.iter().zip(fields) {
+        match (item, field) {
+            (Item::Number(text), Field::Year) => year = Some(expand_year(text)?),
+            (Item::Number(text), Field::Month) if text.len() <= 2 => month = text.parse().ok(),
+            (Item::Month { index, .. }, Field::Month) => month = Some(*index),
+            (Item::Number(text), Field::Day) if text.len() <= 2 => day = text.parse().ok(),
+            _ => return None,
+        }
+    }
+    let year = year.or(reference_year)?;
+    let month = month?;
+    let day = day.unwrap_or(1);
+    if !(1900..=9999).contains(&year) || !(1..=12).contains(&month) || day == 0 {
+        return None;
+    }
+    let leap_day = year == 1900 && month == 2 && day == 29;
+    if day > days_in_month(year, month) && !leap_day {
+        return None;
+    }
+    Some(DateValue::new(year).with_month(month).with_day(day))
+}
+
+fn field_pattern(item: &Item<'_>, field: Field) -> String {
+    match (item, field) {
+        (Item::Month { long: true, .. }, _) => "mmmm".to_string(),
+        (Item::Month { long: false, .. }, _) => "mmm".to_string(),
+        (Item::Number(text), Field::Year) => {
+            if text.len() <= 2 { "yy" } else { "yyyy" }.to_string()
+        }
+        (Item::Number(text), Field::Month) => padded("m", text),
+        (Item::Number(text), Field::Day) => padded("d", text),
+    }
+}
+
+fn padded(token: &str, text: &str) -> String {
+    if text.len() == 2 && text.starts_with('0') {
+        token.repeat(2)
+    } else {
+        token.to_string()
+    }
+}
+
+/// Splits the date into numbers and month names, returning the normalised
+/// separators that sat between them.
+fn lex<'a>(text: &'a str, locale: &Locale) -> Option<(Vec<Item<'a>>, Vec<String>)> {
+    let mut items = Vec::new();
+    let mut separators: Vec<String> = Vec::new();
+    let mut rest = text;
+    let mut pending = String::new();
+
+    while !rest.is_empty() {
+        let sep_len = rest.find(|c: char| !is_separator(c)).unwrap_or(rest.len());
+        if sep_len > 0 {
+            pending.push_str(&normalize_separator(&rest[..sep_len]));
+            rest = &rest[sep_len..];
+            continue;
+        }
+        let word_len = rest.find(is_separator).unwrap_or(rest.len());
+        let word = &rest[..word_len];
+        rest = &rest[word_len..];
+
+        if !items.is_empty() {
+            separators.push(std::mem::take(&mut pending));
+        } else {
+            pending.clear();
+        }
+
+        if word.chars().all(|c| c.is_ascii_digit()) {
+            items.push(Item::Number(word));
+            continue;
+        }
+        if let Some((index, long, dotted)) = match_month(word, locale) {
+            // Abbreviations like "janv." own their trailing period.
+            if dotted && rest.starts_with('.') {
+                rest = &rest[1..];
+            }
+            items.push(Item::Month { index, long });
+            continue;
+        }
+        if items.is_empty() && is_weekday(word, locale) {
+            continue;
+        }
+        return None;
+    }
+
+    if items.is_empty() {
+        return None;
+    }
+    Some((items, separators))
+}
+
+fn is_separator(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '/' | '-' | '.' | ',')
+}
+
+fn normalize_separator(sep: &str) -> String {
+    let mut out = String::new();
+    for ch in sep.chars() {
+        if ch.is_whitespace() {
+            if !out.ends_with(' ') {
+                out.push(' ');
+            }
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+/// Finds the month a word names, preferring full names over abbreviations.
+/// Returns the 1-based month, whether it was the full name, and whether the
+/// matched name ends in a period that the word left off.
+fn match_month(word: &str, locale: &Locale) -> Option<(u8, bool, bool)> {
+    let word = word.to_lowercase();
+    for (names, long) in [(&locale.mmmm, true), (&locale.mmm, false)] {
+        for (idx, name) in names.iter().enumerate() {
+            let name = name.to_lowercase();
+            let bare = name.trim_end_matches('.');
+            if word == name || word == bare {
+                return Some((idx as u8 + 1, long, word == bare && bare != name));
+            }
+        }
+    }
+    None
+}
+
+fn is_weekday(word: &str, locale: &Locale) -> bool {
+    let word = word.to_lowercase();
+    locale.dddd.iter().chain(&locale.ddd).any(|name| {
+        let name = name.to_lowercase();
+        word == name || word == name.trim_end_matches('.')
+    })
+}
+
+fn expand_year(text: &str) -> Option<i32> {
+    let year: i32 = text.parse().ok()?;
+    match text.len() {
+        1 | 2 => Some(if year < 30 { 2000 + year } else { 1900 + year }),
+        4 => Some(year),
+        _ => None,
+    }
+}
+
+fn is_phantom_leap_day(date: &DateValue) -> bool {
+    date.year == 1900 && date.month == Some(2) && date.day == Some(29)
+}
+
+fn time_fraction(date: &DateValue) -> f64 {
+    let seconds = date.hour.unwrap_or(0) as f64 * 3600.0
+        + date.minute.unwrap_or(0) as f64 * 60.0
+        + date.second.unwrap_or(0) as f64
+        + date.millisecond.unwrap_or(0) as f64 / 1000.0;
+    seconds / 86_400.0
+}
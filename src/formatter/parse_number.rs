@@ -0,0 +1,336 @@
+//! Locale-aware parsing of typed-in numbers, the inverse of `format` for
+//! plain, grouped, percent, currency and scientific values.
+
+use std::str::FromStr;
+
+use num_bigint::BigInt;
+
+use crate::constants::{CURRENCY_SYMBOLS, MAX_SAFE_INTEGER};
+
+use super::{
+    locale::{Locale, locale_for_tag},
+    options::FormatterOptions,
+    value::FormatValue,
+};
+
+/// Everything that was recognised while reading a number, kept around so
+/// callers can tell how the value was written as well as what it is.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct NumberScan {
+    pub value: FormatValue<'static>,
+    pub negative: bool,
+    pub parens: bool,
+    pub percent: bool,
+    pub grouping: bool,
+    pub decimals: usize,
+    pub exponential: bool,
+    pub currency: Option<String>,
+    pub currency_after: bool,
+    pub currency_space: bool,
+    /// The minus came after the digits, as in `"12-"`.
+    pub trailing_minus: bool,
+}
+
+/// Parses user-typed text such as `"1.234,56"`, `"(1,200)"`, `"12.5%"`,
+/// `"€ 3,50"` or `"1.2E+3"` into a number using the separators of the
+/// locale selected by `options.locale`.
+///
+/// Integers too large to be represented exactly as `f64` are returned as
+/// `FormatValue::BigInt`. Returns `None` when the text is not a number.
+pub fn parse_number(text: &str, options: &FormatterOptions) -> Option<FormatValue<'static>> {
+    let locale = &locale_for_tag(Some(options.locale.as_str()), options);
+    scan_number(text, locale, &options.grouping).map(|scan| scan.value)
+}
+
+pub(crate) fn scan_number(text: &str, locale: &Locale, grouping: &[u8]) -> Option<NumberScan> {
+    let mut rest = text.trim();
+    let mut parens = false;
+    if let Some(inner) = rest
+        .strip_prefix('(')
+        .and_then(|inner| inner.strip_suffix(')'))
+    {
+        parens = true;
+        rest = inner.trim();
+    }
+
+    let mut affixes = Affixes::default();
+    rest = affixes.strip_prefixes(rest, locale);
+    rest = affixes.strip_suffixes(rest, locale);
+    if rest.is_empty() || (parens && affixes.negative) {
+        return None;
+    }
+
+    let negative = parens || affixes.negative;
+
+    if rest == locale.infinity || rest == "∞" {
+        if affixes.percent || affixes.currency.is_some() {
+            return None;
+        }
+        let value = if negative {
+            f64::NEG_INFINITY
+        } else {
+            f64::INFINITY
+        };
+        return Some(affixes.finish(
+            FormatValue::Number(value),
+            negative,
+            parens,
+            Digits::default(),
+        ));
+    }
+    if rest == locale.nan {
+        if negative || affixes.percent || affixes.currency.is_some() {
+            return None;
+        }
+        return Some(affixes.finish(
+            FormatValue::Number(f64::NAN),
+            false,
+            false,
+            Digits::default(),
+        ));
+    }
+
+    let digits = scan_digits(rest, locale, grouping)?;
+    let value = digits.to_value(negative, affixes.percent)?;
+    Some(affixes.finish(value, negative, parens, digits))
+}
+
+#[derive(Debug, Default)]
+struct Affixes {
+    negative: bool,
+    signed: bool,
+    percent: bool,
+    currency: Option<String>,
+    currency_after: bool,
+    currency_space: bool,
+    trailing_minus: bool,
+}
+
+impl Affixes {
+    fn strip_prefixes<'a>(&mut self, mut rest: &'a str, locale: &Locale) -> &'a str {
+        loop {
+            rest = rest.trim_start();
+            if !self.signed {
+                if let Some(tail) = strip_any_prefix(rest, &[&locale.negative, "-"]) {
+                    self.signed = true;
+                    self.negative = true;
+                    rest = tail;
+                    continue;
+                }
+                if let Some(tail) = strip_any_prefix(rest, &[&locale.positive, "+"]) {
+                    self.signed = true;
+                    rest = tail;
+                    continue;
+                }
+            }
+            if self.currency.is_none()
+                && let Some(symbol) = CURRENCY_SYMBOLS.iter().find(|sym| rest.starts_with(**sym))
+            {
+                self.currency = Some(symbol.to_string());
+                rest = &rest[symbol.len()..];
+                self.currency_space = rest.starts_with(char::is_whitespace);
+                continue;
+            }
+            if !self.percent
+                && let Some(tail) = strip_any_prefix(rest, &[&locale.percent, "%"])
+            {
+                self.percent = true;
+                rest = tail;
+                continue;
+            }
+            return rest;
+        }
+    }
+
+    fn strip_suffixes<'a>(&mut self, mut rest: &'a str, locale: &Locale) -> &'a str {
+        loop {
+            rest = rest.trim_end();
+            if !self.percent
+                && let Some(head) = strip_any_suffix(rest, &[&locale.percent, "%"])
+            {
+                self.percent = true;
+                rest = head;
+                continue;
+            }
+            if self.currency.is_none()
+                && let Some(symbol) = CURRENCY_SYMBOLS.iter().find(|sym| rest.ends_with(**sym))
+            {
+                self.currency = Some(symbol.to_string());
+                self.currency_after = true;
+                rest = &rest[..rest.len() - symbol.len()];
+                self.currency_space = rest.ends_with(char::is_whitespace);
+                continue;
+            }
+            // Excel accepts a trailing minus such as "12-".
+            if !self.signed
+                && let Some(head) = strip_any_suffix(rest, &[&locale.negative, "-"])
+            {
+                self.signed = true;
+                self.negative = true;
+                self.trailing_minus = true;
+                rest = head;
+                continue;
+            }
+            return rest;
+        }
+    }
+
+    fn finish(
+        self,
+        value: FormatValue<'static>,
+        negative: bool,
+        parens: bool,
+        digits: Digits,
+    ) -> NumberScan {
+        NumberScan {
+            value,
+            negative,
+            parens,
+            percent: self.percent,
+            grouping: digits.grouped,
+            decimals: digits.fraction.len(),
+            exponential: digits.exponent.is_some(),
+            currency: self.currency,
+            currency_after: self.currency_after,
+            currency_space: self.currency_space,
+            trailing_minus: self.trailing_minus,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Digits {
+    integer: String,
+    fraction: String,
+    exponent: Option<i32>,
+    grouped: bool,
+}
+
+impl Digits {
+    fn to_value(&self, negative: bool, percent: bool) -> Option<FormatValue<'static>> {
+        let sign = if negative { "-" } else { "" };
+        let integer = if self.integer.is_empty() {
+            "0"
+        } else {
+            self.integer.as_str()
+        };
+
+        if self.fraction.is_empty() && self.exponent.is_none() && !percent {
+            let big = BigInt::from_str(&format!("{sign}{integer}")).ok()?;
+            if big > BigInt::from(MAX_SAFE_INTEGER) || big < BigInt::from(-MAX_SAFE_INTEGER) {
+                return Some(FormatValue::BigInt(big));
+            }
+        }
+
+        // Shift the exponent instead of dividing so "1.1%" reads back as the
+        // exact double for 0.011.
+        let exponent = self.exponent.unwrap_or(0) - if percent { 2 } else { 0 };
+        let literal = format!("{sign}{integer}.{}e{exponent}", self.fraction);
+        let value = f64::from_str(&literal).ok()?;
+        value.is_finite().then_some(FormatValue::Number(value))
+    }
+}
+
+fn scan_digits(input: &str, locale: &Locale, grouping: &[u8]) -> Option<Digits> {
+    let mut digits = Digits::default();
+    let mut rest = input;
+    let mut groups: Vec<usize> = Vec::new();
+    let mut current = 0usize;
+
+    loop {
+        if let Some(ch) = rest.chars().next().filter(char::is_ascii_digit) {
+            digits.integer.push(ch);
+            current += 1;
+            rest = &rest[1..];
+        } else if current > 0
+            && let Some(tail) = strip_group(rest, locale)
+            && tail.starts_with(|c: char| c.is_ascii_digit())
+        {
+            groups.push(current);
+            current = 0;
+            rest = tail;
+        } else {
+            break;
+        }
+    }
+    if !groups.is_empty() {
+        groups.push(current);
+        if !valid_grouping(&groups, grouping) {
+            return None;
+        }
+        digits.grouped = true;
+    }
+
+    if let Some(tail) = strip_any_prefix(rest, &[&locale.decimal]) {
+        rest = tail;
+        let count = rest.chars().take_while(char::is_ascii_digit).count();
+        digits.fraction.push_str(&rest[..count]);
+        rest = &rest[count..];
+    }
+
+    if digits.integer.is_empty() && digits.fraction.is_empty() {
+        return None;
+    }
+
+    if !rest.is_empty() {
+        let tail = strip_any_prefix(rest, &[&locale.exponent, "E", "e"])?;
+        let (negative, tail) = if let Some(t) = strip_any_prefix(tail, &[&locale.negative, "-"]) {
+            (true, t)
+        } else if let Some(t) = strip_any_prefix(tail, &[&locale.positive, "+"]) {
+            (false, t)
+        } else {
+            (false, tail)
+        };
+        if tail.is_empty() || !tail.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let magnitude: i32 = tail.parse().ok()?;
+        digits.exponent = Some(if negative { -magnitude } else { magnitude });
+    }
+
+    Some(digits)
+}
+
+/// Group sizes must follow the configured grouping read from the right: the
+/// last group uses the primary size, earlier ones the secondary size and the
+/// leading group may be shorter.
+fn valid_grouping(groups: &[usize], grouping: &[u8]) -> bool {
+    let primary = grouping.first().copied().unwrap_or(3) as usize;
+    let secondary = grouping.get(1).copied().unwrap_or(primary as u8) as usize;
+    let Some((last, head)) = groups.split_last() else {
+        return true;
+    };
+    if *last != primary {
+        return false;
+    }
+    let Some((first, middle)) = head.split_first() else {
+        return true;
+    };
+    middle.iter().all(|&size| size == secondary) && (1..=secondary).contains(first)
+}
+
+fn strip_group<'a>(input: &'a str, locale: &Locale) -> Option<&'a str> {
+    if let Some(tail) = strip_any_prefix(input, &[&locale.group]) {
+        return Some(tail);
+    }
+    // Space-like group separators are typed as whatever space is at hand.
+    if locale.group.chars().all(char::is_whitespace) {
+        let ch = input.chars().next().filter(|c| c.is_whitespace())?;
+        return Some(&input[ch.len_utf8()..]);
+    }
+    None
+}
+
+fn strip_any_prefix<'a>(input: &'a str, prefixes: &[&str]) -> Option<&'a str> {
+    prefixes
+        .iter()
+        .filter(|p| !p.is_empty())
+        .find_map(|p| input.strip_prefix(*p))
+}
+
+fn strip_any_suffix<'a>(input: &'a str, suffixes: &[&str]) -> Option<&'a str> {
+    suffixes
+        .iter()
+        .filter(|s| !s.is_empty())
+        .find_map(|s| input.strip_suffix(*s))
+}
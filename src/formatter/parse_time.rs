@@ -0,0 +1,255 @@
+//! Parsing of typed-in times of day (`"13:05"`, `"1:05 PM"`,
+//! `"1:05:07.250"`) and elapsed durations (`"37:15:00"`) into day fractions.
+
+use super::{
+    locale::{Locale, locale_for_tag},
+    options::FormatterOptions,
+};
+
+const DAYSIZE: f64 = 86_400.0;
+
+/// A clock time or duration read from text.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TimeScan {
+    pub negative: bool,
+    pub hours: u32,
+    pub minutes: u32,
+    pub seconds: u32,
+    pub subsecond: f64,
+    pub elapsed: bool,
+    pub pattern: String,
+}
+
+impl TimeScan {
+    /// The scanned time as a fraction of a day, the unit serials use.
+    pub fn fraction(&self) -> f64 {
+        let seconds = self.hours as f64 * 3600.0
+            + self.minutes as f64 * 60.0
+            + self.seconds as f64
+            + self.subsecond;
+        let fraction = seconds / DAYSIZE;
+        if self.negative { -fraction } else { fraction }
+    }
+
+    pub fn millisecond(&self) -> u16 {
+        ((self.subsecond * 1000.0).round() as u16).min(999)
+    }
+}
+
+/// Parses a typed-in time into the day-fraction serial that `h`, `[h]`,
+/// `[mm]` and `[ss]` tokens render.
+///
+/// Accepts `h:mm`, `h:mm:ss` and `h:mm:ss.000` with an optional AM/PM marker
+/// (from the locale's `ampm` table or the English `AM`/`PM`/`A`/`P`) after
+/// the time or, as Korean and Chinese write it, before it, and
+/// `m:ss.0` minute-second times. Hours past 23, minutes past 59 in `m:ss.0`
+/// form and a leading minus read as elapsed durations (`"37:15:00"` is
+/// `1.55208…`).
+///
+/// Returns the serial and a format code that renders it the way it was
+/// typed (e.g. `"h:mm AM/PM"` or `"[h]:mm:ss"`), or `None` when the text is
+/// not a time.
+pub fn parse_time(text: &str, options: &FormatterOptions) -> Option<(f64, String)> {
+    let locale = &locale_for_tag(Some(options.locale.as_str()), options);
+    scan_time(text, locale).map(|scan| (scan.fraction(), scan.pattern))
+}
+
+pub(crate) fn scan_time(text: &str, locale: &Locale) -> Option<TimeScan> {
+    let mut rest = text.trim();
+
+    let mut negative = false;
+    if let Some(tail) = rest
+        .strip_prefix(locale.negative.as_str())
+        .or_else(|| rest.strip_prefix('-'))
+    {
+        negative = true;
+        rest = tail.trim_start();
+    }
+
+    let (clock, meridiem) = split_meridiem(rest, locale);
+    if negative && meridiem.is_some() {
+        return None;
+    }
+
+    let (clock, subsecond_digits) = match clock
+        .rsplit_once('.')
+        .or_else(|| clock.rsplit_once(locale.decimal.as_str()))
+    {
+        Some((head, digits))
+            if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) =>
+        {
+            (head, Some(digits))
+        }
+        Some(_) => return None,
+        None => (clock, None),
+    };
+
+    let fields: Vec<&str> = clock.split(':').collect();
+    if !(2..=3).contains(&fields.len())
+        || fields
+            .iter()
+            .any(|f| f.is_empty() || !f.chars().all(|c| c.is_ascii_digit()))
+    {
+        return None;
+    }
+    let values: Vec<u32> = fields
+        .iter()
+        .map(|f| f.parse().ok())
+        .collect::<Option<_>>()?;
+
+    let subsecond = match subsecond_digits {
+        Some(digits) => format!("0.{digits}").parse::<f64>().ok()?,
+        None => 0.0,
+    };
+    let decimals = subsecond_digits.map_or(0, |d| d.len().min(3));
+
+    // "5:07.2" is minutes and seconds; anything else leads with hours.
+    let minute_second = fields.len() == 2 && subsecond_digits.is_some();
+    let (hours, minutes, seconds) = match values.as_slice() {
+        [m, s] if minute_second => (0, *m, *s),
+        [h, m] => (*h, *m, 0),
+        [h, m, s] => (*h, *m, *s),
+        _ => return None,
+    };
+
+    if seconds > 59 || (!minute_second && minutes > 59) {
+        return None;
+    }
+    if fields[1..].iter().any(|f| f.len() != 2) {
+        return None;
+    }
+
+    let mut hours = hours;
+    if let Some(Meridiem { pm, .. }) = meridiem {
+        if minute_second || !(1..=12).contains(&hours) {
+            return None;
+        }
+        hours = match (hours, pm) {
+            (12, false) => 0,
+            (12, true) => 12,
+            (h, true) => h + 12,
+            (h, false) => h,
+        };
+    }
+
+    let elapsed = negative || (minute_second && minutes > 59) || (!minute_second && hours > 23);
+
+    let mut pattern = String::new();
+    if let Some(marker) = meridiem
+        && marker.leading
+    {
+        pattern.push_str(if marker.spaced { "AM/PM " } else { "AM/PM" });
+    }
+    if minute_second {
+        pattern.push_str(if elapsed { "[mm]" } else { "mm" });
+    } else {
+        pattern.push_str(
+            match (elapsed, fields[0].len() == 2 && fields[0].starts_with('0')) {
+                (true, _) => "[h]",
+                (false, true) => "hh",
+                (false, false) => "h",
+            },
+        );
+        pattern.push_str(":mm");
+    }
+    if fields.len() == 3 || minute_second {
+        pattern.push_str(":ss");
+    }
+    if decimals > 0 {
+        pattern.push('.');
+        pattern.push_str(&"0".repeat(decimals));
+    }
+    if meridiem.is_some_and(|marker| !marker.leading) {
+        pattern.push_str(" AM/PM");
+    }
+
+    Some(TimeScan {
+        negative,
+        hours,
+        minutes,
+        seconds,
+        subsecond,
+        elapsed,
+        pattern,
+    })
+}
+
+/// An AM/PM marker read off a time.
+#[derive(Debug, Clone, Copy)]
+struct Meridiem {
+    pm: bool,
+    /// The marker came before the clock digits, as in `"오후 3:30"`.
+    leading: bool,
+    /// Whitespace separated a leading marker from the digits.
+    spaced: bool,
+}
+
+/// Splits an AM/PM marker off the clock digits, trying the end of the text
+/// first and then its start.
+fn split_meridiem<'a>(text: &'a str, locale: &Locale) -> (&'a str, Option<Meridiem>) {
+    let markers = [
+        (
+            locale.ampm.first().map(String::as_str).unwrap_or("AM"),
+            false,
+        ),
+        (locale.ampm.get(1).map(String::as_str).unwrap_or("PM"), true),
+        ("AM", false),
+        ("PM", true),
+        ("A", false),
+        ("P", true),
+    ];
+    let find = |text: &str, markers: &[(&str, bool)]| {
+        let normalized = normalize_marker(text);
+        markers
+            .iter()
+            .find(|(marker, _)| normalize_marker(marker) == normalized)
+            .map(|(_, pm)| *pm)
+    };
+
+    let end = text
+        .rfind(|c: char| c.is_ascii_digit())
+        .map_or(0, |idx| idx + 1);
+    let (clock, tail) = text.split_at(end);
+    let tail = tail.trim();
+    if !tail.is_empty() {
+        return match find(tail, &markers) {
+            Some(pm) => (
+                clock,
+                Some(Meridiem {
+                    pm,
+                    leading: false,
+                    spaced: true,
+                }),
+            ),
+            None => (text, None),
+        };
+    }
+
+    let start = text
+        .find(|c: char| c.is_ascii_digit())
+        .unwrap_or(text.len());
+    let (head, clock) = text.split_at(start);
+    if head.is_empty() {
+        return (text, None);
+    }
+    // Only the locale's own markers lead, so "A1:05" is not a time.
+    match find(head, &markers[..2]) {
+        Some(pm) => (
+            clock,
+            Some(Meridiem {
+                pm,
+                leading: true,
+                spaced: head.trim_end().len() < head.len(),
+            }),
+        ),
+        None => (text, None),
+    }
+}
+
+fn normalize_marker(marker: &str) -> String {
+    marker
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
@@ -0,0 +1,681 @@
+//! Reading formatted text back into the value that produced it, guided by
+//! the section tokens of the pattern that rendered it.
+
+use std::cell::Cell;
+use std::ptr;
+
+use crate::constants::{EPOCH_1900, EPOCH_1904};
+use crate::parser::model::{
+    CalendarKind, ConditionOperator, DateTokenKind, NumberPart, Pattern, Section, SectionToken,
+    Token, TokenKind,
+};
+
+use super::{
+    FormatValue,
+    calendar::{resolve_calendar, section_calendar},
+    era::{EraTable, era_table_for},
+    get_part,
+    locale::Locale,
+    locale_for,
+    options::FormatterOptions,
+    pad::pad,
+    parse_number::scan_number,
+    run_part::token_raw,
+    serial::date_to_serial,
+    to_ymd::to_ymd,
+    value::DateValue,
+};
+
+const DAYSIZE: f64 = 86_400.0;
+
+/// How many tokens a section may try to match before the matcher gives up.
+/// Patterns like `#""#""#` can split a run of digits in exponentially many
+/// ways, and the text is rejected rather than searched to the end.
+const MAX_STEPS: usize = 10_000;
+
+/// Recovers the number that `pattern` rendered as `text`, e.g. reading
+/// `"$1,234.50 CR"` back with `$#,##0.00 "CR"` or `"03-Apr-24"` with
+/// `dd-mmm-yy`.
+///
+/// Each of the numeric partitions is tried in turn: its tokens are matched
+/// against the text, the digits they cover are reassembled, scaling and
+/// percent are undone and the sign is taken from a matched minus or from a
+/// condition that only admits negative values. A reading is only accepted
+/// when formatting it would select the same partition. Dates and times come
+/// back as serials.
+///
+/// Returns `None` when no partition can have produced the text.
+pub fn parse_with_pattern(
+    text: &str,
+    pattern: &Pattern,
+    options: &FormatterOptions,
+) -> Option<f64> {
+    let locale = locale_for(pattern, options);
+    let parts = &pattern.partitions;
+    parts.iter().take(3).find_map(|part| {
+        if part.numerals.is_some() || part.error.is_some() {
+            return None;
+        }
+        let calendar = section_calendar(part, options);
+        let matcher = Matcher {
+            parts,
+            part,
+            options,
+            locale: &locale,
+            calendar,
+            gregorian: resolve_calendar(part, options).is_none(),
+            eras: era_table_for(options, calendar, part.locale.as_deref()),
+            pad: pad('?', options.nbsp),
+            steps: Cell::new(0),
+        };
+        matcher.walk(0, text, &Captures::default())
+    })
+}
+
+/// The pieces of the value picked up while walking a section's tokens.
+#[derive(Debug, Clone, Default)]
+struct Captures {
+    negative: bool,
+    integer: String,
+    fraction: String,
+    exponent: String,
+    exponent_negative: bool,
+    numerator: String,
+    denominator: String,
+    general: Option<f64>,
+    year: Option<i32>,
+    /// The first day of the era an era name matched.
+    era: Option<(i32, u8, u8)>,
+    era_year: Option<i32>,
+    month: Option<u8>,
+    day: Option<u8>,
+    hour: Option<u32>,
+    minute: Option<u32>,
+    second: Option<u32>,
+    subsecond: f64,
+    pm: Option<bool>,
+    elapsed: Option<(DateTokenKind, f64)>,
+    elapsed_negative: bool,
+}
+
+struct Matcher<'a> {
+    parts: &'a [Section],
+    part: &'a Section,
+    options: &'a FormatterOptions,
+    locale: &'a Locale,
+    calendar: CalendarKind,
+    /// Whether dates read as Gregorian, the only calendar they are read
+    /// back from.
+    gregorian: bool,
+    eras: Option<&'a EraTable>,
+    pad: &'static str,
+    steps: Cell<usize>,
+}
+
+impl Matcher<'_> {
+    /// Matches the tokens from `idx` onwards against `text`, backtracking
+    /// over every way a token could have been rendered.
+    fn walk(&self, idx: usize, text: &str, caps: &Captures) -> Option<f64> {
+        if self.steps.get() >= MAX_STEPS {
+            return None;
+        }
+        self.steps.set(self.steps.get() + 1);
+        let Some(token) = self.part.tokens.get(idx) else {
+            return if text.is_empty() {
+                self.finish(caps)
+            } else {
+                None
+            };
+        };
+        self.step(token, text, caps)
+            .into_iter()
+            .find_map(|(rest, next)| self.walk(idx + 1, rest, &next))
+    }
+
+    /// Lists the ways `token` can match the start of `text`, longest first.
+    fn step<'t>(
+        &self,
+        token: &SectionToken,
+        text: &'t str,
+        caps: &Captures,
+    ) -> Vec<(&'t str, Captures)> {
+        let unchanged = |rest: &'t str| (rest, caps.clone());
+        match token {
+            SectionToken::String(tok) => {
+                let rendered = tok.value.replace(' ', self.pad);
+                let mut out: Vec<_> = literal(text, &rendered)
+                    .map(unchanged)
+                    .into_iter()
+                    .collect();
+                if tok.rule.is_some() {
+                    let padded = self.pad.repeat(tok.value.chars().count());
+                    out.extend(literal(text, &padded).map(unchanged));
+                }
+                out
+            }
+            SectionToken::Token(tok) => self.step_token(tok, text, caps),
+            SectionToken::Div => ["/", self.pad, ""]
+                .into_iter()
+                .filter_map(|lit| literal(text, lit).map(unchanged))
+                .collect(),
+            SectionToken::Exp { .. } => literal(text, &self.locale.exponent)
+                .map(unchanged)
+                .into_iter()
+                .collect(),
+            SectionToken::Number(number) => self.step_number(number.part, text, caps),
+            SectionToken::Date(date) => self.step_date(date.kind, date.decimals, text, caps),
+        }
+    }
+
+    fn step_token<'t>(
+        &self,
+        tok: &Token,
+        text: &'t str,
+        caps: &Captures,
+    ) -> Vec<(&'t str, Captures)> {
+        let unchanged = |rest: &'t str| (rest, caps.clone());
+        let literals: Vec<String> = match tok.kind {
+            TokenKind::Minus if tok.volatile && !self.part.date.is_empty() => vec![String::new()],
+            TokenKind::Minus if tok.volatile => {
+                let mut out = Vec::new();
+                if let Some(rest) = literal(text, &self.locale.negative) {
+                    let mut next = caps.clone();
+                    next.negative = true;
+                    out.push((rest, next));
+                }
+                out.push(unchanged(text));
+                return out;
+            }
+            TokenKind::Minus => vec![self.locale.negative.clone()],
+            TokenKind::Plus => vec![self.locale.positive.clone()],
+            TokenKind::General => return self.step_general(text, caps),
+            TokenKind::Text | TokenKind::Error => return Vec::new(),
+            TokenKind::Space => vec![self.pad.to_string(), String::new()],
+            TokenKind::Point if self.part.date.is_empty() => vec![self.locale.decimal.clone()],
+            TokenKind::Fill => match &self.options.fill_char {
+                Some(fill) => vec![format!("{fill}{}", token_raw(tok))],
+                None => vec![String::new()],
+            },
+            TokenKind::Skip => match &self.options.skip_char {
+                Some(skip) => vec![format!("{skip}{}", token_raw(tok))],
+                None => vec![if self.options.nbsp { "\u{00A0}" } else { " " }.to_string()],
+            },
+            TokenKind::Ampm => {
+                let mut markers = vec![("AM", false), ("PM", true)];
+                if tok.short && self.locale.is_default {
+                    markers = vec![("A", false), ("P", true)];
+                } else {
+                    for (idx, marker) in self.locale.ampm.iter().take(2).enumerate() {
+                        markers.insert(idx, (marker.as_str(), idx == 1));
+                    }
+                }
+                return markers
+                    .into_iter()
+                    .filter_map(|(marker, pm)| {
+                        let rest = literal(text, marker)?;
+                        let mut next = caps.clone();
+                        next.pm = Some(pm);
+                        Some((rest, next))
+                    })
+                    .collect();
+            }
+            TokenKind::Percent => vec!["%".to_string()],
+            TokenKind::Locale
+            | TokenKind::Color
+            | TokenKind::Modifier
+            | TokenKind::Condition
+            | TokenKind::NatNum
+            | TokenKind::DbNum
+            | TokenKind::Scale
+            | TokenKind::Comma
+            | TokenKind::Break
+            | TokenKind::Calendar
+            | TokenKind::Duration
+            | TokenKind::DateTime
+            | TokenKind::Hash
+            | TokenKind::Zero
+            | TokenKind::Qmark
+            | TokenKind::Slash
+            | TokenKind::Group => vec![String::new()],
+            _ => vec![token_raw(tok)],
+        };
+        literals
+            .iter()
+            .filter_map(|lit| literal(text, lit).map(unchanged))
+            .collect()
+    }
+
+    fn step_general<'t>(&self, text: &'t str, caps: &Captures) -> Vec<(&'t str, Captures)> {
+        ends(text, |c| {
+            c.is_ascii_digit()
+                || self.locale.decimal.contains(c)
+                || self.locale.exponent.contains(c)
+                || self.locale.positive.contains(c)
+                || self.locale.negative.contains(c)
+        })
+        .into_iter()
+        .filter_map(|end| {
+            let scan = scan_number(&text[..end], self.locale, &self.options.grouping)?;
+            let FormatValue::Number(value) = scan.value else {
+                return None;
+            };
+            if scan.negative || scan.percent || scan.currency.is_some() {
+                return None;
+            }
+            let mut next = caps.clone();
+            next.general = Some(value);
+            Some((&text[end..], next))
+        })
+        .collect()
+    }
+
+    fn step_number<'t>(
+        &self,
+        part: NumberPart,
+        text: &'t str,
+        caps: &Captures,
+    ) -> Vec<(&'t str, Captures)> {
+        let mut text = text;
+        let mut caps = caps.clone();
+        if part == NumberPart::Mantissa && caps.exponent.is_empty() {
+            if let Some(rest) = literal(text, &self.locale.negative) {
+                caps.exponent_negative = true;
+                text = rest;
+            } else if let Some(rest) = literal(text, &self.locale.positive) {
+                text = rest;
+            }
+        }
+        let group = (part == NumberPart::Integer && self.part.grouping)
+            .then_some(self.locale.group.as_str());
+
+        digit_runs(text, self.pad, group)
+            .into_iter()
+            .filter_map(|end| {
+                let run = &text[..end];
+                let digits: String = match part {
+                    NumberPart::Fraction | NumberPart::Denominator => {
+                        run.trim_end_matches(self.pad).to_string()
+                    }
+                    _ => run.trim_start_matches(self.pad).to_string(),
+                };
+                let digits = match group {
+                    Some(group) if !group.is_empty() => digits.replace(group, ""),
+                    _ => digits,
+                };
+                if !digits.chars().all(|c| c.is_ascii_digit()) {
+                    return None;
+                }
+                let mut next = caps.clone();
+                match part {
+                    NumberPart::Integer => next.integer.push_str(&digits),
+                    NumberPart::Fraction => next.fraction.push_str(&digits),
+                    NumberPart::Mantissa => next.exponent.push_str(&digits),
+                    NumberPart::Numerator => next.numerator.push_str(&digits),
+                    NumberPart::Denominator => next.denominator.push_str(&digits),
+                }
+                Some((&text[end..], next))
+            })
+            .collect()
+    }
+
+    fn step_date<'t>(
+        &self,
+        kind: DateTokenKind,
+        decimals: u8,
+        text: &'t str,
+        caps: &Captures,
+    ) -> Vec<(&'t str, Captures)> {
+        let numbers = |min: usize, max: usize| -> Vec<(&'t str, u32)> {
+            ends(text, |c| c.is_ascii_digit())
+                .into_iter()
+                .filter(|end| (min..=max).contains(end))
+                .filter_map(|end| Some((&text[end..], text[..end].parse().ok()?)))
+                .collect()
+        };
+        let with = |rest: &'t str, update: &dyn Fn(&mut Captures)| {
+            let mut next = caps.clone();
+            update(&mut next);
+            (rest, next)
+        };
+
+        let era_years = self.eras.is_some() && self.calendar.uses_eras();
+        match kind {
+            DateTokenKind::Year if era_years => numbers(1, 4)
+                .into_iter()
+                .map(|(rest, y)| with(rest, &|c| c.era_year = Some(y as i32)))
+                .collect(),
+            // Two digits of a Buddhist or Tangun year leave the century open.
+            DateTokenKind::YearShort if era_years => Vec::new(),
+            DateTokenKind::Year => numbers(4, 4)
+                .into_iter()
+                .map(|(rest, y)| with(rest, &|c| c.year = Some(y as i32)))
+                .collect(),
+            DateTokenKind::YearShort => numbers(2, 2)
+                .into_iter()
+                .map(|(rest, y)| with(rest, &|c| c.year = Some(expand_year(y as i32))))
+                .collect(),
+            DateTokenKind::BuddhistYear => numbers(4, 5)
+                .into_iter()
+                .map(|(rest, y)| with(rest, &|c| c.year = Some(y as i32 - 543)))
+                .collect(),
+            DateTokenKind::BuddhistYearShort => numbers(2, 2)
+                .into_iter()
+                .map(|(rest, y)| {
+                    with(rest, &|c| {
+                        c.year = Some(expand_year((y as i32 + 57) % 100));
+                    })
+                })
+                .collect(),
+            DateTokenKind::Month => numbers(1, 2)
+                .into_iter()
+                .map(|(rest, m)| with(rest, &|c| c.month = Some(m as u8)))
+                .collect(),
+            DateTokenKind::MonthName | DateTokenKind::MonthNameShort => {
+                let names = if kind == DateTokenKind::MonthName {
+                    &self.locale.mmmm
+                } else {
+                    &self.locale.mmm
+                };
+                names
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(idx, name)| {
+                        let rest = literal(text, name)?;
+                        Some(with(rest, &|c| c.month = Some(idx as u8 + 1)))
+                    })
+                    .collect()
+            }
+            // A single letter cannot tell the month apart, so it is skipped.
+            DateTokenKind::MonthNameSingle => text
+                .chars()
+                .next()
+                .map(|ch| (&text[ch.len_utf8()..], caps.clone()))
+                .into_iter()
+                .collect(),
+            DateTokenKind::Weekday | DateTokenKind::WeekdayShort => {
+                let names = if kind == DateTokenKind::Weekday {
+                    &self.locale.dddd
+                } else {
+                    &self.locale.ddd
+                };
+                names
+                    .iter()
+                    .filter_map(|name| literal(text, name).map(|rest| (rest, caps.clone())))
+                    .collect()
+            }
+            DateTokenKind::Day => numbers(1, 2)
+                .into_iter()
+                .map(|(rest, d)| with(rest, &|c| c.day = Some(d as u8)))
+                .collect(),
+            DateTokenKind::Hour => numbers(1, 2)
+                .into_iter()
+                .map(|(rest, h)| with(rest, &|c| c.hour = Some(h)))
+                .collect(),
+            DateTokenKind::Minute => numbers(1, 2)
+                .into_iter()
+                .map(|(rest, m)| with(rest, &|c| c.minute = Some(m)))
+                .collect(),
+            DateTokenKind::Second => numbers(1, 2)
+                .into_iter()
+                .map(|(rest, s)| with(rest, &|c| c.second = Some(s)))
+                .collect(),
+            DateTokenKind::Subsecond => {
+                let Some(rest) = literal(text, &self.locale.decimal) else {
+                    return Vec::new();
+                };
+                let len = decimals as usize;
+                let digits = rest
+                    .get(..len)
+                    .filter(|d| d.chars().all(|c| c.is_ascii_digit()));
+                let Some(subsecond) = digits.and_then(|d| format!("0.{d}").parse::<f64>().ok())
+                else {
+                    return Vec::new();
+                };
+                vec![with(&rest[len..], &|c| c.subsecond = subsecond)]
+            }
+            DateTokenKind::HourElapsed
+            | DateTokenKind::MinuteElapsed
+            | DateTokenKind::SecondElapsed => {
+                let (text, negative) = match literal(text, &self.locale.negative) {
+                    Some(rest) if !self.locale.negative.is_empty() => (rest, true),
+                    _ => (text, false),
+                };
+                ends(text, |c| c.is_ascii_digit())
+                    .into_iter()
+                    .filter(|end| *end > 0)
+                    .filter_map(|end| {
+                        let amount: f64 = text[..end].parse().ok()?;
+                        Some(with(&text[end..], &|c| {
+                            c.elapsed = Some((kind, amount));
+                            c.elapsed_negative = negative;
+                        }))
+                    })
+                    .collect()
+            }
+            DateTokenKind::Era => match self.eras {
+                Some(table) => table
+                    .eras()
+                    .iter()
+                    .flat_map(|era| {
+                        [&era.name, &era.short_name, &era.abbreviation]
+                            .into_iter()
+                            .filter_map(|name| literal(text, name))
+                            .map(|rest| with(rest, &|c| c.era = Some(era.start)))
+                    })
+                    .collect(),
+                None => vec![(text, caps.clone())],
+            },
+            DateTokenKind::EraYear if self.eras.is_some() => numbers(1, 3)
+                .into_iter()
+                .map(|(rest, y)| with(rest, &|c| c.era_year = Some(y as i32)))
+                .collect(),
+            DateTokenKind::EraYear => numbers(4, 4)
+                .into_iter()
+                .map(|(rest, y)| with(rest, &|c| c.year = Some(y as i32)))
+                .collect(),
+        }
+    }
+
+    /// Turns the captures into a value and checks that formatting it would
+    /// pick this partition again.
+    fn finish(&self, caps: &Captures) -> Option<f64> {
+        let numeric = self.part.tokens.iter().any(|tok| {
+            matches!(tok, SectionToken::Number(_) | SectionToken::Date(_))
+                || matches!(tok, SectionToken::Token(t) if t.kind == TokenKind::General)
+        });
+        let value = if !self.part.date.is_empty() {
+            self.date_value(caps)?
+        } else if !numeric {
+            // Pure literals such as "zero" stand for the value the
+            // condition pins down.
+            match &self.part.condition {
+                Some(cond) if cond.operator == ConditionOperator::Equal => cond.operand,
+                _ => 0.0,
+            }
+        } else {
+            let magnitude = match caps.general {
+                Some(general) => general,
+                None => self.number_value(caps)?,
+            };
+            let negative = if self.has_volatile_minus() {
+                caps.negative
+            } else {
+                self.negative_only()
+            };
+            if negative { -magnitude } else { magnitude }
+        };
+        let selected = get_part(value, self.parts)?;
+        ptr::eq(selected, self.part).then_some(value)
+    }
+
+    fn number_value(&self, caps: &Captures) -> Option<f64> {
+        let integer = if caps.integer.is_empty() {
+            "0"
+        } else {
+            caps.integer.as_str()
+        };
+        let scale = self.part.scale;
+        if self.part.fractions {
+            let mut value: f64 = integer.parse().ok()?;
+            if !caps.numerator.is_empty() && !caps.denominator.is_empty() {
+                let numerator: f64 = caps.numerator.parse().ok()?;
+                let denominator: f64 = caps.denominator.parse().ok()?;
+                if denominator == 0.0 {
+                    return None;
+                }
+                value += numerator / denominator;
+            }
+            return Some(value / scale);
+        }
+
+        // Shift the exponent instead of dividing so percentages and
+        // thousands scaling read back as the exact doubles.
+        let mut exponent: i32 = if caps.exponent.is_empty() {
+            0
+        } else {
+            caps.exponent.parse().ok()?
+        };
+        if caps.exponent_negative {
+            exponent = -exponent;
+        }
+        exponent -= scale.log10().round() as i32;
+        format!("{integer}.{}e{exponent}", caps.fraction)
+            .parse::<f64>()
+            .ok()
+            .filter(|v| v.is_finite())
+    }
+
+    fn date_value(&self, caps: &Captures) -> Option<f64> {
+        if !self.gregorian {
+            return None;
+        }
+        let minute = caps.minute.unwrap_or(0);
+        let second = caps.second.unwrap_or(0);
+        if let Some((kind, amount)) = caps.elapsed {
+            let seconds = match kind {
+                DateTokenKind::HourElapsed => amount * 3600.0 + (minute * 60 + second) as f64,
+                DateTokenKind::MinuteElapsed => amount * 60.0 + second as f64,
+                _ => amount,
+            } + caps.subsecond;
+            let value = seconds / DAYSIZE;
+            return Some(if caps.elapsed_negative { -value } else { value });
+        }
+
+        let mut hour = caps.hour.unwrap_or(0);
+        if let Some(pm) = caps.pm {
+            if !(1..=12).contains(&hour) {
+                return None;
+            }
+            hour = hour % 12 + if pm { 12 } else { 0 };
+        }
+        if hour > 23 || minute > 59 || second > 59 {
+            return None;
+        }
+        let fraction = ((hour * 3600 + minute * 60 + second) as f64 + caps.subsecond) / DAYSIZE;
+
+        if caps.year.is_none()
+            && caps.era_year.is_none()
+            && caps.month.is_none()
+            && caps.day.is_none()
+        {
+            return Some(fraction);
+        }
+        let month = caps.month.unwrap_or(1);
+        let day = caps.day.unwrap_or(1);
+        let year = match (caps.era, caps.era_year) {
+            (Some(start), Some(era_year)) => {
+                let year = start.0 + era_year - 1;
+                // The date has to fall in the era it was written with.
+                let (era, _) = self.eras?.era_for(year, month, day)?;
+                (era.start == start).then_some(year)?
+            }
+            // Calendars with a single era need no era name.
+            (None, Some(era_year)) => match self.eras?.eras() {
+                [era] => era.start.0 + era_year - 1,
+                _ => return None,
+            },
+            _ => caps.year?,
+        };
+        // 1900-02-29 only exists in Excel's emulation of the Lotus leap bug.
+        if (year, month, day) == (1900, 2, 29) {
+            return (self.options.leap_1900 && !self.options.date_1904).then_some(60.0 + fraction);
+        }
+        let date = DateValue::new(year).with_month(month).with_day(day);
+        let serial = date_to_serial(&date, self.options).ok()?;
+        let system = if self.options.date_1904 {
+            EPOCH_1904
+        } else {
+            EPOCH_1900
+        };
+        let [y, m, d] = to_ymd(serial, system, self.options.leap_1900);
+        ((y, m, d) == (year, month as i32, day as i32)).then_some(serial + fraction)
+    }
+
+    fn has_volatile_minus(&self) -> bool {
+        self.part.tokens.iter().any(
+            |tok| matches!(tok, SectionToken::Token(t) if t.kind == TokenKind::Minus && t.volatile),
+        )
+    }
+
+    /// Whether the partition's condition only admits negative values, in
+    /// which case the sign is dropped when rendering.
+    fn negative_only(&self) -> bool {
+        self.part
+            .condition
+            .as_ref()
+            .is_some_and(|cond| match cond.operator {
+                ConditionOperator::Less => cond.operand <= 0.0,
+                ConditionOperator::LessEqual | ConditionOperator::Equal => cond.operand < 0.0,
+                _ => false,
+            })
+    }
+}
+
+fn literal<'t>(text: &'t str, lit: &str) -> Option<&'t str> {
+    text.strip_prefix(lit)
+}
+
+/// Byte offsets at which a run of characters accepted by `accept` could
+/// end, longest first and down to the empty run.
+fn ends(text: &str, accept: impl Fn(char) -> bool) -> Vec<usize> {
+    let mut out = vec![0];
+    for (idx, ch) in text.char_indices() {
+        if !accept(ch) {
+            break;
+        }
+        out.push(idx + ch.len_utf8());
+    }
+    out.reverse();
+    out
+}
+
+/// Like [`ends`] for digit placeholders, which render as digits, `?`
+/// padding and, in grouped integers, the group separator.
+fn digit_runs(text: &str, pad: &str, group: Option<&str>) -> Vec<usize> {
+    let mut out = vec![0];
+    let mut pos = 0;
+    loop {
+        let rest = &text[pos..];
+        let step = if rest.starts_with(|c: char| c.is_ascii_digit()) {
+            1
+        } else if rest.starts_with(pad) {
+            pad.len()
+        } else if let Some(group) = group.filter(|g| !g.is_empty() && rest.starts_with(*g)) {
+            group.len()
+        } else {
+            break;
+        };
+        pos += step;
+        out.push(pos);
+    }
+    out.reverse();
+    out
+}
+
+fn expand_year(short: i32) -> i32 {
+    if short < 30 {
+        2000 + short
+    } else {
+        1900 + short
+    }
+}
@@ -0,0 +1,71 @@
+//! The registry entries a pattern's sections format with, looked up once
+//! per pattern and options so that formatting a value takes no locks.
+//! Patterns without calendars or numeral systems resolve to nothing.
+
+use std::sync::Arc;
+
+use crate::parser::model::{CalendarKind, Pattern, Section};
+
+use super::calendar::{Calendar, resolve_calendar};
+use super::numeral::{NumeralSystem, numeral_system};
+use super::options::FormatterOptions;
+
+/// What one section of a pattern formats with.
+#[derive(Debug, Clone)]
+pub(super) struct ResolvedSection {
+    /// The calendar the section shows dates in, when it is not Gregorian.
+    pub calendar: Option<Arc<dyn Calendar>>,
+    /// The system a `[DBNumN]` or `[NatNumN]` tag writes numbers in, for
+    /// the section's locale.
+    pub numerals: Option<Arc<dyn NumeralSystem>>,
+}
+
+/// A section with nothing to look up.
+static PLAIN: ResolvedSection = ResolvedSection {
+    calendar: None,
+    numerals: None,
+};
+
+/// Resolves the sections of `pattern`, in order, or none at all when no
+/// section has a calendar or numeral system to look up.
+pub(super) fn resolve_sections(
+    pattern: &Pattern,
+    options: &FormatterOptions,
+) -> Vec<ResolvedSection> {
+    if !pattern
+        .partitions
+        .iter()
+        .any(|part| needs_lookup(part, options))
+    {
+        return Vec::new();
+    }
+    pattern
+        .partitions
+        .iter()
+        .map(|part| {
+            let locale = part
+                .locale
+                .as_deref()
+                .or((!options.locale.is_empty()).then_some(options.locale.as_str()));
+            ResolvedSection {
+                calendar: (!part.date.is_empty())
+                    .then(|| resolve_calendar(part, options))
+                    .flatten(),
+                numerals: part.numerals.and_then(|tag| numeral_system(tag, locale)),
+            }
+        })
+        .collect()
+}
+
+/// The entries for section `idx`, plain for sections `resolve_sections`
+/// skipped.
+pub(super) fn resolved_section(sections: &[ResolvedSection], idx: usize) -> &ResolvedSection {
+    sections.get(idx).unwrap_or(&PLAIN)
+}
+
+fn needs_lookup(part: &Section, options: &FormatterOptions) -> bool {
+    let calendar = part.calendar != CalendarKind::Gregorian
+        || options.calendar.is_some()
+        || options.custom_calendar.is_some();
+    part.numerals.is_some() || (!part.date.is_empty() && calendar)
+}
@@ -1,34 +1,40 @@
 use std::borrow::Cow;
-use std::ptr;
 
 use num_bigint::BigInt;
 use num_traits::ToPrimitive;
 
-use crate::constants::{DateUnits, MAX_L_DATE, MAX_S_DATE, MIN_L_DATE, MIN_S_DATE};
+use crate::constants::{
+    DateUnits, EPOCH_1900, EPOCH_1904, MAX_L_DATE, MAX_S_DATE, MAX_SAFE_INTEGER, MIN_L_DATE,
+    MIN_S_DATE,
+};
 use crate::parser::model::{
     DateToken, DateTokenKind, NumberPart, NumberToken, Section, SectionToken, StringRule, Token,
     TokenKind,
 };
 
 use super::{
+    calendar::{CalendarDate, section_calendar},
+    era::era_table_for,
     error::FormatterError,
     general::format_general,
-    locale::{Locale, default_locale},
+    locale::Locale,
     math::{clamp, dec2frac, get_exponent, get_significand, round},
+    numeral::{NumeralSystem, spell_runs, transliterate},
     options::FormatterOptions,
     pad::pad,
-    serial::date_from_serial,
+    resolved::ResolvedSection,
+    serial::{DAYS_1900_TO_1904, date_from_serial},
+    value::DurationValue,
 };
 
 const DAYSIZE: f64 = 86_400.0;
-const MAX_SAFE_INTEGER: i128 = 9_007_199_254_740_991;
-const MIN_SAFE_INTEGER: i128 = -9_007_199_254_740_991;
 
 #[derive(Debug, Clone)]
 pub enum RunValue<'a> {
     Number(f64),
     BigInt(&'a BigInt),
     Text(Cow<'a, str>),
+    Duration(DurationValue),
 }
 
 impl<'a> From<f64> for RunValue<'a> {
@@ -55,40 +61,18 @@ impl<'a> From<String> for RunValue<'a> {
     }
 }
 
-pub fn run_part(
+pub(super) fn run_part(
     value: RunValue<'_>,
     part: &Section,
+    resolved: &ResolvedSection,
     opts: &FormatterOptions,
     locale: &Locale,
 ) -> Result<String, FormatterError> {
-    if let Some(db_num_type) = part.db_num {
-        if part.date.is_empty() {
-            if let Some(num) = match value {
-                RunValue::Number(n) => Some(n),
-                RunValue::BigInt(big) => big.to_f64(),
-                RunValue::Text(_) => None,
-            } {
-                let res = match db_num_type {
-                    crate::parser::model::DbNumType::TradSimp => {
-                        super::chinese::to_chinese_numeral(num, false, true)
-                    }
-                    crate::parser::model::DbNumType::TradFormal => {
-                        super::chinese::to_chinese_numeral(num, true, true)
-                    }
-                    crate::parser::model::DbNumType::Simp => {
-                        super::chinese::to_chinese_digits(num, false)
-                    }
-                    crate::parser::model::DbNumType::FullWidth => super::chinese::to_full_width(num),
-                };
-                return Ok(res);
-            }
-        }
-    }
-
+    let numerals = &resolved.numerals;
     let mut numeric_value = match value {
         RunValue::Number(n) => Some(n),
         RunValue::BigInt(big) => {
-            if BigInt::from(MIN_SAFE_INTEGER) <= *big && *big <= BigInt::from(MAX_SAFE_INTEGER) {
+            if BigInt::from(-MAX_SAFE_INTEGER) <= *big && *big <= BigInt::from(MAX_SAFE_INTEGER) {
                 big.to_f64()
             } else {
                 return Ok(if opts.bigint_error_number {
@@ -99,6 +83,11 @@ pub fn run_part(
             }
         }
         RunValue::Text(_) => None,
+        RunValue::Duration(span) => Some(span.as_days()),
+    };
+    let duration = match &value {
+        RunValue::Duration(span) => Some(*span),
+        _ => None,
     };
 
     let text_value = match &value {
@@ -245,22 +234,50 @@ pub fn run_part(
     let group_pri = group_pri_raw as usize;
     let group_sec = group_sec_raw as usize;
 
+    let calendar = section_calendar(part, opts);
+    let custom = resolved.calendar.as_ref();
+    let mut custom_day = None;
+    // Serials count Gregorian days; other calendars convert from those.
+    let date_system = if opts.date_1904 {
+        EPOCH_1904
+    } else {
+        EPOCH_1900
+    };
+    // The 1904 system shows negative dates and times as their magnitude
+    // behind a minus sign.
+    let negative_1904 = date_system == EPOCH_1904
+        && !part.date.is_empty()
+        && numeric_value.is_some_and(|n| n < 0.0);
+    if negative_1904 {
+        numeric_value = numeric_value.map(f64::abs);
+    }
+
+    // Excel has no negative times in the 1900 system.
+    if !part.date.is_empty() && duration.is_some_and(|span| span.negative) && !negative_1904 {
+        return Ok(opts.overflow.clone());
+    }
+
     if !part.date.is_empty()
         && let Some(num) = numeric_value
     {
-        date = num.trunc();
-        let t = DAYSIZE * (num - date);
-        time = t.floor();
-        subsec = t - time;
+        if let Some(span) = duration {
+            // Whole days and seconds straight from the span, without the
+            // rounding error of a day fraction.
+            let seconds = span.duration.as_secs();
+            date = (seconds / 86_400) as f64;
+            time = (seconds % 86_400) as f64;
+            subsec = f64::from(span.duration.subsec_nanos()) / 1e9;
+        } else {
+            date = num.trunc();
+            let t = DAYSIZE * (num - date);
+            time = t.floor();
+            subsec = t - time;
+        }
         if subsec.abs() < 1e-6 {
             subsec = 0.0;
         } else if subsec > 0.9999 {
             subsec = 0.0;
             time += 1.0;
-            if (time - DAYSIZE).abs() < f64::EPSILON {
-                time = 0.0;
-                date += 1.0;
-            }
         }
         if subsec != 0.0 {
             let has_msec = part.date.contains(DateUnits::MILLISECOND);
@@ -280,8 +297,15 @@ pub fn run_part(
                 subsec = 0.0;
             }
         }
-        if date != 0.0 || part.date_system != 0 {
-            let dt = date_from_serial(num, part.date_system, opts.leap_1900);
+        // A time rounded up to midnight starts the next day.
+        let mut day_serial = num.floor();
+        if time >= DAYSIZE {
+            time = 0.0;
+            date += 1.0;
+            day_serial += 1.0;
+        }
+        if date != 0.0 || date_system != 0 {
+            let dt = date_from_serial(day_serial, date_system, opts.leap_1900);
             year = dt[0];
             month = dt[1] as u8;
             day = dt[2];
@@ -292,16 +316,22 @@ pub fn run_part(
             minute = ((x as i64 / 60) % 60) as i32;
             hour = (((x as i64 / 60) / 60) % 60) as i32;
         }
-        weekday = ((6.0 + date).rem_euclid(7.0)) as usize;
+        // Bounds and weekdays are counted on the 1900 calendar.
+        let shift = if date_system == EPOCH_1904 {
+            DAYS_1900_TO_1904
+        } else {
+            0.0
+        };
+        weekday = ((6.0 + date + shift).rem_euclid(7.0)) as usize;
 
-        let overflow_val = date + (time / DAYSIZE);
-        if date_overflows(num, overflow_val, opts.date_span_large) {
+        let overflow_val = date + shift + (time / DAYSIZE);
+        if date_overflows(num + shift, overflow_val, opts.date_span_large) {
             if opts.date_error_throws {
                 return Err(FormatterError::DateOutOfBounds);
             }
             if opts.date_error_number {
                 let mut buffer = String::new();
-                if num < 0.0 {
+                if num < 0.0 || negative_1904 {
                     buffer.push_str(&locale.negative);
                 }
                 format_general(&mut buffer, num, part, locale);
@@ -309,14 +339,28 @@ pub fn run_part(
             }
             return Ok(opts.overflow.clone());
         }
+        if let Some(custom) = custom {
+            // Serial 60 in Hijri calendars and days outside the Umm
+            // al-Qura table have no date.
+            let Some(found) = custom.day((date + shift) as i64) else {
+                return invalid_date(opts);
+            };
+            weekday = usize::from(found.weekday % 7);
+            custom_day = Some(found);
+        }
     }
 
     let pad_q = pad('?', opts.nbsp);
+    let eras = era_table_for(opts, calendar, part.locale.as_deref());
+    let mut calendar_date = CalendarDate::new(calendar, eras, year, month, day);
+    if let (Some(custom), Some(found)) = (custom, custom_day) {
+        calendar_date = calendar_date.in_custom(custom.clone(), found);
+    }
 
     if exponent < 0 {
-        mantissa_sign = "-".to_string();
+        mantissa_sign = locale.negative.clone();
     } else if part.exp_plus {
-        mantissa_sign = "+".to_string();
+        mantissa_sign = locale.positive.clone();
     }
 
     let mut output = String::new();
@@ -349,6 +393,9 @@ pub fn run_part(
         has_integer_digit || has_fraction_digit || has_numerator_digit || general_has_value;
     let show_negative_sign = negative_value && has_value_digits;
 
+    // Literal text and values a numeral system already wrote, which it
+    // leaves alone afterwards.
+    let mut verbatim = Vec::new();
     for (idx, token) in part.tokens.iter().enumerate() {
         match token {
             SectionToken::String(tok) => {
@@ -382,7 +429,9 @@ pub fn run_part(
                     }
                     None => tok.value.replace(' ', pad_q),
                 };
+                let start = output.len();
                 output.push_str(&value);
+                verbatim.push(start..output.len());
             }
             SectionToken::Token(tok) => match tok.kind {
                 TokenKind::Space => {
@@ -391,15 +440,18 @@ pub fn run_part(
                     }
                 }
                 TokenKind::Error => output.push_str(&opts.invalid),
-                TokenKind::Point => {
-                    if part.date.is_empty() {
-                        output.push_str(&locale.decimal);
-                    } else {
-                        output.push_str(&token_raw(tok));
-                    }
-                }
+                TokenKind::Point if part.date.is_empty() => output.push_str(&locale.decimal),
+                TokenKind::Point => output.push_str(&token_raw(tok)),
                 TokenKind::General => {
-                    if let Some(num) = numeric_value {
+                    if let Some(num) = numeric_value
+                        && let Some(system) = numerals
+                    {
+                        // Numeral systems write `General` values whole,
+                        // sign included.
+                        let start = output.len();
+                        output.push_str(&system.format(num));
+                        verbatim.push(start..output.len());
+                    } else if let Some(num) = numeric_value {
                         format_general(&mut output, num, part, locale);
                     } else if let Some(text) = text_value {
                         output.push_str(text);
@@ -408,6 +460,8 @@ pub fn run_part(
                 TokenKind::Minus => {
                     if tok.volatile && !part.date.is_empty() {
                         // no-op
+                    } else if tok.volatile && part.general && numerals.is_some() {
+                        // The numeral system writes the sign with the value.
                     } else if tok.volatile && numeric_value.is_none_or(|n| n >= 0.0) {
                         // skip volatile minus for non-negative numeric values or non-numeric inputs
                     } else if tok.volatile
@@ -447,7 +501,7 @@ pub fn run_part(
                 }
                 TokenKind::Ampm => {
                     let idx = if hour < 12 { 0 } else { 1 };
-                    if tok.short && ptr::eq(locale, default_locale()) {
+                    if tok.short && locale.is_default {
                         output.push(if idx == 0 { 'A' } else { 'P' });
                     } else if let Some(val) = locale.ampm.get(idx) {
                         output.push_str(val);
@@ -455,7 +509,9 @@ pub fn run_part(
                 }
                 TokenKind::Percent => output.push('%'),
                 TokenKind::Digit | TokenKind::Char | TokenKind::String | TokenKind::Escaped => {
+                    let start = output.len();
                     output.push_str(&token_raw(tok));
+                    verbatim.push(start..output.len());
                 }
                 TokenKind::Locale
                 | TokenKind::Color
@@ -609,9 +665,8 @@ pub fn run_part(
                 date_token,
                 part,
                 locale,
-                year,
-                month,
-                day,
+                &calendar_date,
+                numerals.as_deref(),
                 weekday,
                 hour,
                 minute,
@@ -627,6 +682,21 @@ pub fn run_part(
         }
     }
 
+    if negative_1904 {
+        output.insert_str(0, &locale.negative);
+    }
+    // Systems that spell numbers out rewrite the numbers of the formatted
+    // text, and dates write their fields themselves; place-value systems
+    // swap the digits.
+    if let Some(system) = numerals
+        && !part.text
+    {
+        if !system.spells_out() {
+            output = transliterate(system.as_ref(), &output);
+        } else if part.date.is_empty() {
+            output = spell_runs(system.as_ref(), &output, &verbatim, locale);
+        }
+    }
     Ok(output)
 }
 
@@ -675,7 +745,7 @@ fn is_fraction_component(token: &SectionToken) -> bool {
     }
 }
 
-fn token_raw(token: &Token) -> String {
+pub(crate) fn token_raw(token: &Token) -> String {
     match &token.value {
         crate::parser::model::TokenValue::Text(text) => text.clone(),
         crate::parser::model::TokenValue::Char(ch) => ch.to_string(),
@@ -758,15 +828,22 @@ fn append_fraction_denominator(
     chunk_len
 }
 
+/// What a serial with no date in the section's calendar renders as.
+fn invalid_date(opts: &FormatterOptions) -> Result<String, FormatterError> {
+    if opts.date_error_throws {
+        return Err(FormatterError::InvalidDate);
+    }
+    Ok(opts.invalid.clone())
+}
+
 #[allow(clippy::too_many_arguments)]
 fn append_date_token(
     output: &mut String,
     token: &DateToken,
     part: &Section,
     locale: &Locale,
-    year: i32,
-    month: u8,
-    day: i32,
+    calendar_date: &CalendarDate,
+    numerals: Option<&dyn NumeralSystem>,
     weekday: usize,
     hour: i32,
     minute: i32,
@@ -776,60 +853,105 @@ fn append_date_token(
     time: f64,
     numeric_value: f64,
 ) {
-    if let Some(db_num_type) = part.db_num {
-        let is_formal = matches!(
-            db_num_type,
-            crate::parser::model::DbNumType::TradFormal
-        );
-        let y = year
-            .to_string()
-            .chars()
-            .map(|c| {
-                let digit = c.to_digit(10).unwrap() as f64;
-                match db_num_type {
-                    crate::parser::model::DbNumType::Simp => super::chinese::to_chinese_digits(digit, false),
-                    crate::parser::model::DbNumType::FullWidth => super::chinese::to_full_width(digit),
-                    _ => super::chinese::to_chinese_numeral(digit, is_formal, false),
-                }
-            })
-            .collect::<String>();
-        let m = match db_num_type {
-            crate::parser::model::DbNumType::TradFormal => super::chinese::to_chinese_numeral(month as f64, true, true),
-            _ => super::chinese::to_chinese_numeral(month as f64, false, false),
-        };
-        let d = match db_num_type {
-            crate::parser::model::DbNumType::TradFormal => super::chinese::to_chinese_numeral(day as f64, true, true),
-            _ => super::chinese::to_chinese_numeral(day as f64, false, false),
+    let &CalendarDate {
+        calendar,
+        year,
+        month,
+        day,
+        gregorian_year,
+        era,
+        ..
+    } = calendar_date;
+    if let Some(system) = numerals
+        && system.spells_out()
+    {
+        // Spelled-out systems write the numeric fields; names stay the
+        // locale's.
+        let text = match token.kind {
+            DateTokenKind::Year | DateTokenKind::YearShort => Some(system.format_year(year)),
+            DateTokenKind::Month => Some(system.format_date_field(month.into())),
+            DateTokenKind::Day => Some(system.format_date_field(day)),
+            _ => None,
         };
-        match token.kind {
-            DateTokenKind::Year | DateTokenKind::YearShort => output.push_str(&y),
-            DateTokenKind::Month | DateTokenKind::MonthName | DateTokenKind::MonthNameShort => {
-                output.push_str(&m)
-            }
-            DateTokenKind::Day | DateTokenKind::Weekday | DateTokenKind::WeekdayShort => {
-                output.push_str(&d)
+        if let Some(text) = text {
+            output.push_str(&text);
+            return;
+        }
+    }
+    if let Some((custom, found)) = &calendar_date.custom {
+        let text = match token.kind {
+            DateTokenKind::Year | DateTokenKind::YearShort | DateTokenKind::EraYear => {
+                custom.numeral(year)
             }
-            _ => {}
+            DateTokenKind::Month => custom.numeral(month.into()),
+            DateTokenKind::Day => custom.numeral(day),
+            DateTokenKind::MonthName => custom.month_name(year, month, false),
+            DateTokenKind::MonthNameShort => custom.month_name(year, month, true),
+            DateTokenKind::MonthNameSingle => custom
+                .month_name(year, month, false)
+                .and_then(|name| name.chars().next())
+                .map(String::from),
+            DateTokenKind::Weekday => custom.weekday_name(found.weekday, false),
+            DateTokenKind::WeekdayShort => custom.weekday_name(found.weekday, true),
+            // A custom calendar has no eras unless it names them.
+            DateTokenKind::Era => Some(
+                custom
+                    .era_name(found, token.width.unwrap_or(1))
+                    .unwrap_or_default(),
+            ),
+            _ => None,
+        };
+        if let Some(text) = text {
+            output.push_str(&text);
+            return;
         }
-        return;
     }
     match token.kind {
         DateTokenKind::Year => {
+            // Era calendars write the year of the era unpadded.
+            if calendar.uses_eras() && era.is_some() {
+                output.push_str(&year.to_string());
+                return;
+            }
             if year < 0 {
                 output.push_str(&locale.negative);
             }
             output.push_str(&format!("{:04}", year.abs()));
         }
         DateTokenKind::YearShort => {
-            let y = year % 100;
-            output.push_str(&format!("{:02}", y.abs()));
+            output.push_str(&format!("{:02}", (year % 100).abs()));
+        }
+        DateTokenKind::Era => {
+            if let Some((era, _)) = era {
+                output.push_str(match token.width.unwrap_or(1) {
+                    1 => &era.abbreviation,
+                    2 => &era.short_name,
+                    _ => &era.name,
+                });
+            }
+        }
+        DateTokenKind::EraYear => {
+            match era {
+                Some((_, era_year)) => {
+                    if token.zero_pad && era_year < 10 {
+                        output.push('0');
+                    }
+                    output.push_str(&era_year.to_string());
+                }
+                // Without eras the year counts from the common era.
+                None => {
+                    if year < 0 {
+                        output.push_str(&locale.negative);
+                    }
+                    output.push_str(&format!("{:04}", year.abs()));
+                }
+            }
         }
-        DateTokenKind::Era => {}
         DateTokenKind::BuddhistYear => {
-            output.push_str(&(year + 543).to_string());
+            output.push_str(&(gregorian_year + 543).to_string());
         }
         DateTokenKind::BuddhistYearShort => {
-            let y = (year + 543) % 100;
+            let y = (gregorian_year + 543) % 100;
             output.push_str(&format!("{:02}", y));
         }
         DateTokenKind::Month => {
@@ -839,7 +961,7 @@ fn append_date_token(
             output.push_str(&month.to_string());
         }
         DateTokenKind::MonthNameSingle => {
-            let source = if part.date_system == crate::constants::EPOCH_1317 {
+            let source = if calendar.is_hijri() {
                 &locale.mmmm6
             } else {
                 &locale.mmmm
@@ -852,7 +974,7 @@ fn append_date_token(
             }
         }
         DateTokenKind::MonthNameShort => {
-            let source = if part.date_system == crate::constants::EPOCH_1317 {
+            let source = if calendar.is_hijri() {
                 &locale.mmm6
             } else {
                 &locale.mmm
@@ -862,7 +984,7 @@ fn append_date_token(
             }
         }
         DateTokenKind::MonthName => {
-            let source = if part.date_system == crate::constants::EPOCH_1317 {
+            let source = if calendar.is_hijri() {
                 &locale.mmmm6
             } else {
                 &locale.mmmm
@@ -1,8 +1,219 @@
-use super::{to_ymd::to_ymd, value::DateValue};
+//! Excel serial numbers: days counted from the epoch of a date system,
+//! with the time of day as the fraction.
+//!
+//! These are the conversions the formatter renders with, so a serial read
+//! here shows the same date a pattern prints for it.
+
+use thiserror::Error;
+
+use crate::constants::{
+    EPOCH_1317, EPOCH_1900, EPOCH_1904, EPOCH_UMALQURA, MAX_L_DATE, MAX_S_DATE, MIN_L_DATE,
+    MIN_S_DATE,
+};
+
+use super::{
+    FormatterError,
+    calendar::{hijri_to_days, system_calendar, umalqura_to_days},
+    options::FormatterOptions,
+    timezone::TimeZone,
+    to_ymd::to_ymd,
+    value::DateValue,
+};
 
 const DAYSIZE: f64 = 86_400.0;
+/// 1970-01-01 in the 1904 date system.
+const UNIX_EPOCH_1904: f64 = 24_107.0;
+/// 1970-01-01 in the 1900 date system.
+const UNIX_EPOCH_1900: f64 = 25_569.0;
+/// Serial 0 of the 1904 system in the 1900 system.
+pub(super) const DAYS_1900_TO_1904: f64 = 1_462.0;
+
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum SerialError {
+    #[error("serial {0} is outside the supported date range")]
+    OutOfRange(f64),
+    #[error("the date has no serial in this date system")]
+    InvalidDate,
+    #[error("unknown date system {0}")]
+    UnknownSystem(i32),
+}
+
+impl DateValue {
+    /// The serial of the date's wall clock in `system` (`EPOCH_1900`,
+    /// `EPOCH_1904`, or `EPOCH_1317` and `EPOCH_UMALQURA`, whose fields
+    /// are Hijri). With
+    /// `leap_1900` the 1900 system keeps Lotus' phantom 1900-02-29 as
+    /// serial 60; without it serials count real days throughout.
+    ///
+    /// The zone is ignored. Missing fields count as the start of their
+    /// unit, and serials outside `MIN_L_DATE..MAX_L_DATE` are an error.
+    pub fn to_serial(&self, system: i32, leap_1900: bool) -> Result<f64, SerialError> {
+        let year = self.year;
+        let month = self.month.unwrap_or(1);
+        let day = self.day.unwrap_or(1);
+        let hour = self.hour.unwrap_or(0);
+        let minute = self.minute.unwrap_or(0);
+        let second = self.second.unwrap_or(0);
+        if hour > 23 || minute > 59 || second > 59 {
+            return Err(SerialError::InvalidDate);
+        }
+        let subsecond = match self.nanosecond {
+            Some(ns) => f64::from(ns) / 1e9,
+            None => f64::from(self.millisecond.unwrap_or(0)) / 1000.0,
+        };
+        let fraction =
+            (f64::from(hour) * 3600.0 + f64::from(minute) * 60.0 + f64::from(second) + subsecond)
+                / DAYSIZE;
+
+        let date = match system {
+            EPOCH_1317 | EPOCH_UMALQURA => hijri_to_serial(system, year, month, day)?,
+            EPOCH_1900 if leap_1900 && (year, month, day) == (1900, 2, 29) => 60.0,
+            // Serial 0 reads as the day before 1900-01-01.
+            EPOCH_1900 if leap_1900 && (year, month, day) == (1900, 1, 0) => 0.0,
+            EPOCH_1900 | EPOCH_1904 => {
+                if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+                    return Err(SerialError::InvalidDate);
+                }
+                let days = days_from_civil(year, month.into(), day.into()) as f64;
+                serial_from_days(days, system, leap_1900).ok_or(SerialError::InvalidDate)?
+            }
+            _ => return Err(SerialError::UnknownSystem(system)),
+        };
+        check_serial(date + fraction, system, true)
+    }
+
+    /// The date and time `serial` shows in `system`, to the millisecond.
+    /// Negative 1904 serials are negative times rather than dates, and an
+    /// error.
+    pub fn from_serial(serial: f64, system: i32, leap_1900: bool) -> Result<Self, SerialError> {
+        if !is_known_system(system) {
+            return Err(SerialError::UnknownSystem(system));
+        }
+        if system == EPOCH_1904 && serial < 0.0 {
+            return Err(SerialError::OutOfRange(serial));
+        }
+        check_serial(serial, system, true)?;
+        let [mut year, mut month, mut day, hour, minute, second] =
+            date_from_serial(serial, system, leap_1900);
+        if let Some(calendar) = system_calendar(system) {
+            let (date, _) = split_serial(serial);
+            let shown = calendar.day(date as i64).ok_or(SerialError::InvalidDate)?;
+            (year, month, day) = (shown.year, shown.month.into(), shown.day.into());
+        }
+        let t = DAYSIZE * (serial - serial.floor());
+        // A second within 0.0001 of the next was already rounded up.
+        let millisecond = if t - t.floor() > 0.9999 {
+            0
+        } else {
+            (((t - t.floor()) * 1000.0).round() as u16).min(999)
+        };
+        let mut value = DateValue::new(year)
+            .with_month(month as u8)
+            .with_day(day as u8)
+            .with_time(hour as u8, minute as u8, second as u8);
+        if millisecond > 0 {
+            value = value.with_millisecond(millisecond);
+        }
+        Ok(value)
+    }
+
+    /// The day of the week, 0 for Sunday, on the proleptic Gregorian
+    /// calendar. Serial weekdays follow `weekday` instead.
+    pub fn weekday(&self) -> u8 {
+        let days = days_from_civil(
+            self.year,
+            u32::from(self.month.unwrap_or(1)),
+            u32::from(self.day.unwrap_or(1)),
+        );
+        // 1970-01-01 was a Thursday.
+        (days + 4).rem_euclid(7) as u8
+    }
+}
+
+/// Days in a Gregorian month.
+pub(super) fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn is_known_system(system: i32) -> bool {
+    matches!(
+        system,
+        EPOCH_1900 | EPOCH_1904 | EPOCH_1317 | EPOCH_UMALQURA
+    )
+}
+
+/// The serial showing a Hijri date. Hijri serials always count the
+/// phantom 1900-02-29, and dates before the first serial have none.
+fn hijri_to_serial(system: i32, year: i32, month: u8, day: u8) -> Result<f64, SerialError> {
+    let days = if system == EPOCH_UMALQURA {
+        umalqura_to_days(year, month, day)
+    } else {
+        hijri_to_days(year, month, day)
+    }
+    .ok_or(SerialError::InvalidDate)?;
+    let serial = serial_from_days(days as f64, EPOCH_1900, true).ok_or(SerialError::InvalidDate)?;
+    check_serial(serial, system, true)?;
+    let shown = system_calendar(system).and_then(|calendar| calendar.day(serial as i64));
+    if shown.is_none_or(|shown| (shown.year, shown.month, shown.day) != (year, month, day)) {
+        return Err(SerialError::InvalidDate);
+    }
+    Ok(serial)
+}
+
+/// The day of the week `serial` shows in `system`, 0 for Sunday. Like
+/// Excel this counts the phantom 1900-02-29, so 1900-01-01 is a Sunday.
+pub fn weekday(serial: f64, system: i32) -> Result<u8, SerialError> {
+    if !is_known_system(system) {
+        return Err(SerialError::UnknownSystem(system));
+    }
+    let serial = check_serial(serial, system, true)?;
+    let (serial, shift) = if system == EPOCH_1904 {
+        (serial.abs(), DAYS_1900_TO_1904)
+    } else {
+        (serial, 0.0)
+    };
+    // A time rounded up to midnight falls on the next day.
+    let (day, _) = split_serial(serial);
+    let date = serial.trunc() + (day - serial.floor());
+    Ok((6.0 + date + shift).rem_euclid(7.0) as u8)
+}
+
+/// Checks that `serial` is a date the formatter renders rather than
+/// replacing with the overflow string: within `MIN_L_DATE..MAX_L_DATE`
+/// with `span_large`, or Excel's `MIN_S_DATE..MAX_S_DATE` without.
+pub fn check_serial(serial: f64, system: i32, span_large: bool) -> Result<f64, SerialError> {
+    let value = if system == EPOCH_1904 {
+        serial.abs() + DAYS_1900_TO_1904
+    } else {
+        serial
+    };
+    let (min, max) = if span_large {
+        (MIN_L_DATE, MAX_L_DATE)
+    } else {
+        (MIN_S_DATE, MAX_S_DATE)
+    };
+    if value.is_nan() || value < min || value >= max {
+        return Err(SerialError::OutOfRange(serial));
+    }
+    Ok(serial)
+}
 
-pub fn date_to_serial(date: &DateValue, _ignore_timezone: bool) -> Option<f64> {
+/// Converts a date to a serial in the date system `options` selects. The
+/// 1904 system has no phantom leap day and no dates before 1904.
+///
+/// A date with a zone is moved to `options.time_zone` when one is set and
+/// `options.ignore_timezone` is off; otherwise its wall clock is used as
+/// is. Unknown zone ids are an error, as are dates the system has no
+/// serial for.
+pub(super) fn date_to_serial(
+    date: &DateValue,
+    options: &FormatterOptions,
+) -> Result<f64, FormatterError> {
     let month = date.month.unwrap_or(1) as u32;
     let day = date.day.unwrap_or(1) as u32;
     let year = date.year;
@@ -11,28 +222,90 @@ pub fn date_to_serial(date: &DateValue, _ignore_timezone: bool) -> Option<f64> {
     let second = date.second.unwrap_or(0) as i64;
     let millisecond = date.millisecond.unwrap_or(0) as i64;
 
-    let days = days_from_civil(year, month, day);
-    let seconds = hour * 3600 + minute * 60 + second;
-    let fraction = (seconds as f64 + millisecond as f64 / 1000.0) / DAYSIZE;
-    let d = days as f64 + fraction;
-    let offset = if d <= -25_509.0 { -25_568.0 } else { -25_569.0 };
-    Some(d - offset)
+    let mut seconds =
+        days_from_civil(year, month, day) * DAYSIZE as i64 + hour * 3600 + minute * 60 + second;
+    if !options.ignore_timezone
+        && let (Some(from), Some(to)) = (&date.zone, &options.time_zone)
+    {
+        let utc = from.to_utc(seconds).ok_or_else(|| zone_error(from))?;
+        seconds = utc + i64::from(to.offset_at(utc).ok_or_else(|| zone_error(to))?);
+    }
+    let days = seconds.div_euclid(DAYSIZE as i64);
+    let subsecond = match date.nanosecond {
+        Some(ns) => ns as f64 / 1e9,
+        None => millisecond as f64 / 1000.0,
+    };
+    let fraction = (seconds.rem_euclid(DAYSIZE as i64) as f64 + subsecond) / DAYSIZE;
+    let system = if options.date_1904 {
+        EPOCH_1904
+    } else {
+        EPOCH_1900
+    };
+    let serial = serial_from_days(days as f64, system, options.leap_1900)
+        .ok_or(FormatterError::DateOutOfBounds)?;
+    Ok(serial + fraction)
+}
+
+/// Why `zone` could not place a date: an id missing from the database, or
+/// an instant outside the range it covers.
+fn zone_error(zone: &TimeZone) -> FormatterError {
+    match zone {
+        TimeZone::Named(id) if TimeZone::parse(id).is_none() => {
+            FormatterError::UnknownTimeZone(id.clone())
+        }
+        _ => FormatterError::DateOutOfBounds,
+    }
 }
 
-pub fn date_from_serial(serial: f64, system: i32, leap1900: bool) -> [i32; 6] {
-    let floor = serial.floor();
-    let t = DAYSIZE * (serial - floor);
+/// The serial of a day counted from 1970-01-01. With `leap_1900` the days
+/// up to 1900-02-28 sit one serial lower to make room for the phantom
+/// 1900-02-29, and 1899-12-30 falls between serials.
+fn serial_from_days(days: f64, system: i32, leap_1900: bool) -> Option<f64> {
+    if system == EPOCH_1904 {
+        // Negative 1904 serials show as negative times, not earlier dates.
+        let serial = days + UNIX_EPOCH_1904;
+        return (serial >= 0.0).then_some(serial);
+    }
+    if leap_1900 {
+        // 1899-12-30, 1899-12-31 and 1900-03-01.
+        match days {
+            -25_569.0 => return None,
+            -25_568.0..-25_508.0 => return Some(days + UNIX_EPOCH_1900 - 1.0),
+            _ => {}
+        }
+    }
+    Some(days + UNIX_EPOCH_1900)
+}
+
+/// Splits a serial into its day and the second of that day. Times within
+/// 0.0001 seconds of the next second round up to it, and into the next
+/// day at midnight.
+fn split_serial(serial: f64) -> (f64, f64) {
+    let mut date = serial.floor();
+    let t = DAYSIZE * (serial - date);
     let mut time = t.floor();
-    if (t - time) > 0.9999 {
+    if t - time > 0.9999 {
         time += 1.0;
-        if (time - DAYSIZE).abs() < f64::EPSILON {
+        if time >= DAYSIZE {
             time = 0.0;
+            date += 1.0;
         }
     }
+    (date, time)
+}
 
-    let [y, m, d] = to_ymd(serial, system, leap1900);
-    let x = if time < 0.0 { DAYSIZE + time } else { time };
-    let total_seconds = x as i64;
+/// Splits a serial into Gregorian date and time fields. Negative serials
+/// in the 1904 system read as their magnitude, which Excel shows behind a
+/// minus sign.
+pub(super) fn date_from_serial(serial: f64, system: i32, leap1900: bool) -> [i32; 6] {
+    let serial = if system == EPOCH_1904 {
+        serial.abs()
+    } else {
+        serial
+    };
+    let (date, time) = split_serial(serial);
+    let [y, m, d] = to_ymd(date, system, leap1900);
+    let total_seconds = time as i64;
     let hh = ((total_seconds / 60) / 60) % 60;
     let mm = (total_seconds / 60) % 60;
     let ss = total_seconds % 60;
@@ -40,7 +313,7 @@ pub fn date_from_serial(serial: f64, system: i32, leap1900: bool) -> [i32; 6] {
     [y, m, d, hh as i32, mm as i32, ss as i32]
 }
 
-fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+pub(super) fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
     let y = year - (month <= 2) as i32;
     let era = if y >= 0 { y } else { y - 399 } / 400;
     let yoe = y - era * 400;
@@ -0,0 +1,129 @@
+//! Time zones for date values.
+//!
+//! Zone ids resolve against the IANA time zone database that the opt-in
+//! `tzdb` feature bundles through jiff, with its full history of offset
+//! and daylight saving changes. Without the feature only fixed offsets
+//! are known, which keeps the database out of builds that never name a
+//! zone.
+
+/// The zone a date is given in or rendered in.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TimeZone {
+    /// A fixed offset from UTC, in seconds east of Greenwich.
+    Offset(i32),
+    /// An IANA zone id such as `Europe/Berlin`.
+    Named(String),
+}
+
+impl TimeZone {
+    pub fn utc() -> Self {
+        TimeZone::Offset(0)
+    }
+
+    /// Reads `UTC`, `Z`, offsets such as `+05:30` or `-0800`, or a zone id
+    /// from the time zone database.
+    pub fn parse(text: &str) -> Option<TimeZone> {
+        let text = text.trim();
+        if text.eq_ignore_ascii_case("z") {
+            return Some(TimeZone::utc());
+        }
+        if let Some(offset) = parse_offset(text) {
+            return Some(TimeZone::Offset(offset));
+        }
+        tzdb::lookup(text).map(|_| TimeZone::Named(text.to_string()))
+    }
+
+    /// Seconds east of UTC at the instant `utc` (seconds since 1970), or
+    /// `None` for zone ids missing from the database.
+    pub fn offset_at(&self, utc: i64) -> Option<i32> {
+        match self {
+            TimeZone::Offset(seconds) => Some(*seconds),
+            TimeZone::Named(id) => tzdb::offset_at(&tzdb::lookup(id)?, utc),
+        }
+    }
+
+    /// The instant at which the zone's clocks read `local` (seconds since
+    /// 1970 on the wall clock). Skipped wall times resolve with the offset
+    /// from before the jump and repeated ones to their first occurrence.
+    pub fn to_utc(&self, local: i64) -> Option<i64> {
+        match self {
+            TimeZone::Offset(seconds) => Some(local - i64::from(*seconds)),
+            TimeZone::Named(id) => tzdb::to_utc(&tzdb::lookup(id)?, local),
+        }
+    }
+}
+
+#[cfg(feature = "tzdb")]
+mod tzdb {
+    use jiff::{Timestamp, tz::TimeZone};
+
+    pub(super) fn lookup(id: &str) -> Option<TimeZone> {
+        TimeZone::get(id).ok()
+    }
+
+    pub(super) fn offset_at(zone: &TimeZone, utc: i64) -> Option<i32> {
+        let instant = Timestamp::from_second(utc).ok()?;
+        Some(zone.to_offset(instant).seconds())
+    }
+
+    pub(super) fn to_utc(zone: &TimeZone, local: i64) -> Option<i64> {
+        let wall = Timestamp::from_second(local)
+            .ok()?
+            .to_zoned(TimeZone::UTC)
+            .datetime();
+        let instant = zone.to_ambiguous_timestamp(wall).compatible().ok()?;
+        Some(instant.as_second())
+    }
+}
+
+#[cfg(not(feature = "tzdb"))]
+mod tzdb {
+    /// Stands in for a zone when there is no database to find one in.
+    pub(super) enum Unknown {}
+
+    pub(super) fn lookup(_id: &str) -> Option<Unknown> {
+        None
+    }
+
+    pub(super) fn offset_at(zone: &Unknown, _utc: i64) -> Option<i32> {
+        match *zone {}
+    }
+
+    pub(super) fn to_utc(zone: &Unknown, _local: i64) -> Option<i64> {
+        match *zone {}
+    }
+}
+
+fn parse_offset(text: &str) -> Option<i32> {
+    let rest = text
+        .strip_prefix("UTC")
+        .or_else(|| text.strip_prefix("GMT"))
+        .unwrap_or(text);
+    if rest.is_empty() && !text.is_empty() {
+        return Some(0);
+    }
+    let (sign, digits) = if let Some(digits) = rest.strip_prefix('+') {
+        (1, digits)
+    } else if let Some(digits) = rest.strip_prefix(['-', '\u{2212}']) {
+        (-1, digits)
+    } else {
+        return None;
+    };
+    let digits: String = digits.chars().filter(|c| *c != ':').collect();
+    if digits.is_empty() || digits.len() > 4 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let (hours, minutes) = if digits.len() <= 2 {
+        (digits.parse::<i32>().ok()?, 0)
+    } else {
+        let split = digits.len() - 2;
+        (
+            digits[..split].parse::<i32>().ok()?,
+            digits[split..].parse::<i32>().ok()?,
+        )
+    };
+    if hours > 18 || minutes > 59 {
+        return None;
+    }
+    Some(sign * (hours * 3_600 + minutes * 60))
+}
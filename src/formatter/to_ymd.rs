@@ -1,4 +1,4 @@
-use crate::constants::{EPOCH_1317, EPOCH_1904};
+use crate::constants::EPOCH_1904;
 
 fn to_ymd_1900(ord: i32, leap1900: bool) -> [i32; 3] {
     if leap1900 && ord >= 0 {
@@ -31,40 +31,9 @@ fn to_ymd_1904(ord: i32) -> [i32; 3] {
     to_ymd_1900(ord + 1_462, false)
 }
 
-fn to_ymd_1317(ord: i32) -> [i32; 3] {
-    if ord == 60 {
-        panic!("#VALUE!");
-    }
-    if ord <= 1 {
-        return [1317, 8, 29];
-    }
-    if ord < 60 {
-        return [1317, if ord < 32 { 9 } else { 10 }, 1 + ((ord - 2) % 30)];
-    }
-
-    let y = 10_631_f64 / 30.0;
-    let shift1 = 8.01 / 60.0;
-    let mut z = ord as f64 + 466_935.0;
-    let cyc = (z / 10_631.0).floor();
-    z -= 10_631.0 * cyc;
-    let j = ((z - shift1) / y).floor();
-    z -= (j * y + shift1).floor();
-    let m = ((z + 28.5001) / 29.5).floor();
-    if (m as i32) == 13 {
-        return [30 * cyc as i32 + j as i32, 12, 30];
-    }
-    [
-        30 * cyc as i32 + j as i32,
-        m as i32,
-        (z - (29.5001 * m - 29.0).floor()).round() as i32,
-    ]
-}
-
+/// The Gregorian year, month and day `ord` shows in `system`.
 pub fn to_ymd(ord: f64, system: i32, leap1900: bool) -> [i32; 3] {
     let int = ord.floor() as i32;
-    if system == EPOCH_1317 {
-        return to_ymd_1317(int);
-    }
     if system == EPOCH_1904 {
         return to_ymd_1904(int);
     }
@@ -1,7 +1,11 @@
 use std::borrow::Cow;
+use std::ops::Neg;
+use std::time::Duration;
 
 use num_bigint::BigInt;
 
+use super::timezone::TimeZone;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum FormatValue<'a> {
     Number(f64),
@@ -10,6 +14,7 @@ pub enum FormatValue<'a> {
     Boolean(bool),
     Null,
     Date(DateValue),
+    Duration(DurationValue),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -21,6 +26,10 @@ pub struct DateValue {
     pub minute: Option<u8>,
     pub second: Option<u8>,
     pub millisecond: Option<u16>,
+    /// Nanoseconds into the second; takes precedence over `millisecond`.
+    pub nanosecond: Option<u32>,
+    /// The zone the fields are wall-clock time in, if known.
+    pub zone: Option<TimeZone>,
 }
 
 impl DateValue {
@@ -33,6 +42,8 @@ impl DateValue {
             minute: None,
             second: None,
             millisecond: None,
+            nanosecond: None,
+            zone: None,
         }
     }
 
@@ -57,6 +68,76 @@ impl DateValue {
         self.millisecond = Some(ms);
         self
     }
+
+    pub fn with_nanosecond(mut self, ns: u32) -> Self {
+        self.nanosecond = Some(ns);
+        self
+    }
+
+    pub fn with_zone(mut self, zone: TimeZone) -> Self {
+        self.zone = Some(zone);
+        self
+    }
+
+    /// Sets a fixed UTC offset, in seconds east of Greenwich.
+    pub fn with_offset(self, seconds: i32) -> Self {
+        self.with_zone(TimeZone::Offset(seconds))
+    }
+}
+
+/// A signed span of time kept to the nanosecond. Elapsed-time tokens such
+/// as `[h]` and `[ss].000` read it without going through a day fraction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DurationValue {
+    pub duration: Duration,
+    pub negative: bool,
+}
+
+impl DurationValue {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            negative: false,
+        }
+    }
+
+    /// Builds a duration from seconds and nanoseconds that share a sign,
+    /// as signed duration types split them.
+    pub fn from_secs_nanos(seconds: i64, nanoseconds: i32) -> Self {
+        let total = i128::from(seconds) * 1_000_000_000 + i128::from(nanoseconds);
+        let magnitude = total.unsigned_abs();
+        Self {
+            duration: Duration::new(
+                (magnitude / 1_000_000_000) as u64,
+                (magnitude % 1_000_000_000) as u32,
+            ),
+            negative: total < 0,
+        }
+    }
+
+    /// The span as a fraction of days, the way a serial holds it.
+    pub fn as_days(&self) -> f64 {
+        let days = (self.duration.as_secs() as f64 + f64::from(self.duration.subsec_nanos()) / 1e9)
+            / 86_400.0;
+        if self.negative { -days } else { days }
+    }
+}
+
+impl Neg for DurationValue {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            duration: self.duration,
+            negative: !self.negative && !self.duration.is_zero(),
+        }
+    }
+}
+
+impl From<Duration> for DurationValue {
+    fn from(value: Duration) -> Self {
+        Self::new(value)
+    }
 }
 
 impl<'a> From<f64> for FormatValue<'a> {
@@ -100,3 +181,15 @@ impl<'a> From<DateValue> for FormatValue<'a> {
         Self::Date(value)
     }
 }
+
+impl<'a> From<DurationValue> for FormatValue<'a> {
+    fn from(value: DurationValue) -> Self {
+        Self::Duration(value)
+    }
+}
+
+impl<'a> From<Duration> for FormatValue<'a> {
+    fn from(value: Duration) -> Self {
+        Self::Duration(value.into())
+    }
+}
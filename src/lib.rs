@@ -8,8 +8,18 @@ pub mod wasm;
 #[cfg(feature = "typst-plugin")]
 pub mod typst_plugin;
 
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+pub use formatter::FromSerial;
 pub use formatter::{
-    ColorValue, DateValue, FormatValue, FormatterError, FormatterOptions, LocaleSettings,
-    add_locale, format, format_color, format_with_options,
+    BuiltinNumerals, Calendar, CalendarDay, ColorValue, CompiledFormat, DateValue, DurationValue,
+    Era, EraTable, FormatValue, FormatterError, FormatterOptions, Locale, LocaleRegistry,
+    LocaleSettings, NumeralSystem, PatternCacheStats, TimeZone, add_calendar, add_locale,
+    add_numeral_system, clear_pattern_cache, format, format_color, format_with_options,
+    get_locale_settings, infer_value_and_format, list_locales, parse_date, parse_number,
+    parse_time, parse_with_pattern, pattern_cache_stats, remove_calendar, remove_locale,
+    remove_numeral_system, set_pattern_cache_capacity,
+};
+pub use parser::{
+    CurrencyPlacement, FormatCategory, FormatInfo, NegativeStyle, NumberFormatKind,
+    NumberFormatSpec, format_info, parse_format_section, parse_pattern, tokenize,
 };
-pub use parser::{parse_format_section, parse_pattern, tokenize};
@@ -0,0 +1,201 @@
+//! Toolbar-style edits on parsed patterns: adding and removing decimals,
+//! switching the thousands separator and picking how negatives look.
+//!
+//! Edits work on the section tokens and go back through the canonical
+//! writer and `parse_pattern`, so literals, colors, conditions and locale
+//! tags come out as they went in.
+
+use super::error::ParseError;
+use super::model::{
+    Color, NumberPart, NumberToken, Pattern, Section, SectionToken, StringToken, Token, TokenKind,
+    TokenValue,
+};
+use super::pattern::parse_pattern;
+use super::serialize::is_conditional;
+
+/// How the negative section of a number format shows negative values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NegativeStyle {
+    /// `-1234.10`
+    Minus,
+    /// `(1234.10)`
+    Parentheses,
+    /// `1234.10` in red
+    Red,
+    /// `-1234.10` in red
+    RedMinus,
+    /// `(1234.10)` in red
+    RedParentheses,
+}
+
+impl Pattern {
+    /// Shows one more decimal place in every numeric section.
+    pub fn increase_decimals(&self) -> Result<Pattern, ParseError> {
+        self.edit_numeric(|section| {
+            if let Some(last) = last_number_mut(section, NumberPart::Fraction) {
+                last.pattern.push('0');
+                return;
+            }
+            let Some(mut idx) = last_number_index(section, NumberPart::Integer) else {
+                return;
+            };
+            // A bare `0.` already has its point; the digit goes after it.
+            if let Some(point) = section
+                .tokens
+                .iter()
+                .position(|tok| matches!(tok, SectionToken::Token(t) if t.kind == TokenKind::Point))
+            {
+                idx = point;
+            } else {
+                let point = Token::new(TokenKind::Point, ".", TokenValue::Text(".".to_string()));
+                section.tokens.insert(idx + 1, SectionToken::Token(point));
+                idx += 1;
+            }
+            section.tokens.insert(
+                idx + 1,
+                SectionToken::Number(NumberToken::new(NumberPart::Fraction, "0")),
+            );
+        })
+    }
+
+    /// Shows one decimal place less in every numeric section, dropping the
+    /// decimal point along with the last one.
+    pub fn decrease_decimals(&self) -> Result<Pattern, ParseError> {
+        self.edit_numeric(|section| {
+            let Some(idx) = last_number_index(section, NumberPart::Fraction) else {
+                return;
+            };
+            if let SectionToken::Number(number) = &mut section.tokens[idx] {
+                number.pattern.pop();
+                if !number.pattern.is_empty() {
+                    return;
+                }
+            }
+            section.tokens.remove(idx);
+            if last_number_index(section, NumberPart::Fraction).is_none() {
+                section.tokens.retain(
+                    |tok| !matches!(tok, SectionToken::Token(t) if t.kind == TokenKind::Point),
+                );
+            }
+        })
+    }
+
+    /// Switches the thousands separator on or off in every numeric section,
+    /// following the first one.
+    pub fn toggle_grouping(&self) -> Result<Pattern, ParseError> {
+        let grouping = !self
+            .partitions
+            .iter()
+            .find(|part| is_numeric(part))
+            .is_some_and(|part| part.grouping);
+        self.edit_numeric(|section| {
+            if section.int_pattern.len() == 1 && !section.exponential {
+                section.grouping = grouping;
+            }
+        })
+    }
+
+    /// Rewrites the negative section from the positive one in the given
+    /// style. Patterns with conditions have no fixed negative section and
+    /// come back unchanged.
+    pub fn with_negative_style(&self, style: NegativeStyle) -> Result<Pattern, ParseError> {
+        let mut pattern = self.clone();
+        if is_conditional(&pattern)
+            || pattern.partitions.len() < 3
+            || !is_numeric(&pattern.partitions[0])
+        {
+            return parse_pattern(&pattern.to_string());
+        }
+
+        let parens = matches!(
+            style,
+            NegativeStyle::Parentheses | NegativeStyle::RedParentheses
+        );
+        let positive = &mut pattern.partitions[0];
+        let aligned = matches!(
+            positive.tokens.last(),
+            Some(SectionToken::Token(tok)) if tok.kind == TokenKind::Skip && tok.raw == "_)"
+        );
+        if parens && !aligned {
+            let skip = Token::new(TokenKind::Skip, "_)", TokenValue::Text(")".to_string()));
+            positive.tokens.push(SectionToken::Token(skip));
+        } else if !parens && aligned {
+            positive.tokens.pop();
+        }
+
+        let mut negative = pattern.partitions[0].clone();
+        negative.color = None;
+        if parens {
+            // Drop the `_)` spacer; the closing parenthesis takes its place.
+            negative.tokens.pop();
+            negative.tokens.insert(0, string_token("("));
+            negative.tokens.push(string_token(")"));
+        }
+        match style {
+            NegativeStyle::Minus => {
+                // Without user-written sections after it the negative
+                // section can be left to the parser.
+                let trailing = pattern.partitions[2..].iter().any(|part| !part.generated);
+                negative
+                    .tokens
+                    .insert(0, SectionToken::Token(Token::minus(!trailing)));
+                negative.generated = !trailing;
+            }
+            NegativeStyle::RedMinus => {
+                negative
+                    .tokens
+                    .insert(0, SectionToken::Token(Token::minus(false)));
+                negative.color = Some(Color::Named("red".to_string()));
+            }
+            NegativeStyle::Red | NegativeStyle::RedParentheses => {
+                negative.color = Some(Color::Named("red".to_string()));
+            }
+            NegativeStyle::Parentheses => {}
+        }
+        negative.condition = pattern.partitions[1].condition.clone();
+        pattern.partitions[1] = negative;
+        parse_pattern(&pattern.to_string())
+    }
+
+    fn edit_numeric(&self, edit: impl Fn(&mut Section)) -> Result<Pattern, ParseError> {
+        let mut pattern = self.clone();
+        for section in pattern
+            .partitions
+            .iter_mut()
+            .filter(|part| is_numeric(part))
+        {
+            edit(section);
+        }
+        parse_pattern(&pattern.to_string())
+    }
+}
+
+/// Sections showing plain digits: not General, fractions, dates or text.
+fn is_numeric(section: &Section) -> bool {
+    section.date.is_empty()
+        && !section.general
+        && !section.fractions
+        && section
+            .tokens
+            .iter()
+            .any(|tok| matches!(tok, SectionToken::Number(_)))
+}
+
+fn last_number_index(section: &Section, part: NumberPart) -> Option<usize> {
+    section
+        .tokens
+        .iter()
+        .rposition(|tok| matches!(tok, SectionToken::Number(number) if number.part == part))
+}
+
+fn last_number_mut(section: &mut Section, part: NumberPart) -> Option<&mut NumberToken> {
+    let idx = last_number_index(section, part)?;
+    match &mut section.tokens[idx] {
+        SectionToken::Number(number) => Some(number),
+        _ => None,
+    }
+}
+
+fn string_token(value: &str) -> SectionToken {
+    SectionToken::String(StringToken::new(value))
+}
@@ -0,0 +1,136 @@
+//! Introspection of parsed patterns, the equivalent of numfmt's
+//! `getFormatInfo`.
+
+use crate::constants::{CURRENCY_SYMBOLS, DateUnits};
+
+use super::error::ParseError;
+use super::model::{Color, Pattern, Section, SectionToken};
+use super::pattern::parse_pattern;
+
+/// The broad kind of value a format code is meant for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FormatCategory {
+    General,
+    Number,
+    Currency,
+    Percent,
+    Fraction,
+    Scientific,
+    Date,
+    Time,
+    DateTime,
+    Text,
+}
+
+impl FormatCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FormatCategory::General => "general",
+            FormatCategory::Number => "number",
+            FormatCategory::Currency => "currency",
+            FormatCategory::Percent => "percent",
+            FormatCategory::Fraction => "fraction",
+            FormatCategory::Scientific => "scientific",
+            FormatCategory::Date => "date",
+            FormatCategory::Time => "time",
+            FormatCategory::DateTime => "datetime",
+            FormatCategory::Text => "text",
+        }
+    }
+}
+
+/// A summary of what a format code renders, read off its first section.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatInfo {
+    pub category: FormatCategory,
+    /// Most decimal places shown: fraction digits for numbers, subsecond
+    /// digits for times.
+    pub max_decimals: usize,
+    pub grouping: bool,
+    /// Multiplier applied before rendering: 100 for percent, 0.001 per
+    /// trailing scaling comma.
+    pub scale: f64,
+    /// Whether negative numbers are wrapped in parentheses.
+    pub parentheses: bool,
+    pub date_units: DateUnits,
+    /// The color of each partition, in positive, negative, zero, text order.
+    pub colors: Vec<Option<Color>>,
+}
+
+/// Parses `pattern` and describes it.
+pub fn format_info(pattern: &str) -> Result<FormatInfo, ParseError> {
+    parse_pattern(pattern).map(|parsed| FormatInfo::from(&parsed))
+}
+
+impl From<&Pattern> for FormatInfo {
+    fn from(pattern: &Pattern) -> Self {
+        let default = Section::new();
+        // A lone "@" leaves generated number sections in front of it.
+        let first = pattern
+            .partitions
+            .iter()
+            .find(|part| !part.generated)
+            .or(pattern.partitions.first())
+            .unwrap_or(&default);
+        let max_decimals = if first.date.is_empty() {
+            first.frac_max
+        } else {
+            first.sec_decimals as usize
+        };
+        FormatInfo {
+            category: category(first),
+            max_decimals,
+            grouping: first.grouping,
+            scale: first.scale,
+            parentheses: pattern.partitions.get(1).is_some_and(|part| part.parens),
+            date_units: first.date,
+            colors: pattern
+                .partitions
+                .iter()
+                .map(|part| part.color.clone())
+                .collect(),
+        }
+    }
+}
+
+fn category(section: &Section) -> FormatCategory {
+    let calendar = DateUnits::YEAR | DateUnits::MONTH | DateUnits::DAY;
+    let has_number = section
+        .tokens
+        .iter()
+        .any(|tok| matches!(tok, SectionToken::Number(_)));
+
+    if !section.date.is_empty() {
+        match (
+            section.date.intersects(calendar),
+            section.date.intersects(!calendar),
+        ) {
+            (true, true) => FormatCategory::DateTime,
+            (false, true) => FormatCategory::Time,
+            _ => FormatCategory::Date,
+        }
+    } else if section.general {
+        FormatCategory::General
+    } else if section.text && !has_number {
+        FormatCategory::Text
+    } else if section.exponential {
+        FormatCategory::Scientific
+    } else if section.fractions {
+        FormatCategory::Fraction
+    } else if section.percent {
+        FormatCategory::Percent
+    } else if has_currency(section) {
+        FormatCategory::Currency
+    } else if has_number {
+        FormatCategory::Number
+    } else {
+        FormatCategory::Text
+    }
+}
+
+fn has_currency(section: &Section) -> bool {
+    section.tokens.iter().any(|tok| {
+        matches!(tok, SectionToken::String(s)
+            if CURRENCY_SYMBOLS.iter().any(|sym| s.value.contains(sym)))
+    })
+}
@@ -1,14 +1,22 @@
 pub mod error;
 pub mod model;
 
+mod edit;
+mod info;
 mod pattern;
 mod section;
+mod serialize;
+mod spec;
 mod tokenizer;
 
+pub use edit::NegativeStyle;
+pub use info::{FormatCategory, FormatInfo, format_info};
 pub use model::{
-    Color, Condition, ConditionOperator, DateToken, DateTokenKind, NumberPart, NumberToken,
-    Pattern, Section, SectionToken, StringRule, StringToken, Token, TokenKind, TokenValue,
+    CalendarKind, Color, Condition, ConditionOperator, DateToken, DateTokenKind, NumberPart,
+    NumberToken, NumeralTag, Pattern, Section, SectionToken, StringRule, StringToken, Token,
+    TokenKind, TokenValue,
 };
 pub use pattern::parse_pattern;
 pub use section::{SectionParseResult, parse_format_section};
+pub use spec::{CurrencyPlacement, NumberFormatKind, NumberFormatSpec};
 pub use tokenizer::tokenize;
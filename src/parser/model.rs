@@ -114,12 +114,87 @@ pub enum Color {
     Index(u32),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum DbNumType {
-    TradSimp,   // DBNum1
-    TradFormal, // DBNum2
-    Simp,       // DBNum3
-    FullWidth,  // DBNum4
+/// A `[DBNumN]` or `[NatNumN]` tag, naming the numeral system a section
+/// writes its numbers in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NumeralTag {
+    DbNum(u8),
+    NatNum(u8),
+}
+
+/// The calendar a section shows dates in, from the calendar byte of a
+/// Windows locale code (`[$-30411]` or `[$-th-TH,107]`) or from `B1`/`B2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CalendarKind {
+    #[default]
+    Gregorian,
+    /// Japanese imperial eras.
+    Japanese,
+    /// Minguo years, counted from 1912.
+    Taiwan,
+    /// Tangun years, counted from 2333 BC.
+    Korean,
+    /// The tabular Hijri calendar.
+    Hijri,
+    /// Buddhist years, counted from 543 BC.
+    Thai,
+    /// The Hebrew lunisolar calendar, written in Hebrew letters.
+    Hebrew,
+    /// The Saudi Umm al-Qura Hijri calendar.
+    UmAlQura,
+    /// A calendar code with no built-in calendar. Dates show in the
+    /// calendar registered for it with `add_calendar`, else as Gregorian.
+    Custom(u8),
+}
+
+impl CalendarKind {
+    /// The calendar for a calendar byte. The Gregorian variants read as
+    /// Gregorian and codes without a built-in calendar as `Custom`.
+    pub fn from_code(code: u8) -> Self {
+        match code {
+            0x00..=0x02 | 0x09..=0x0c => Self::Gregorian,
+            0x03 => Self::Japanese,
+            0x04 => Self::Taiwan,
+            0x05 => Self::Korean,
+            0x06 => Self::Hijri,
+            0x07 => Self::Thai,
+            0x08 => Self::Hebrew,
+            0x17 => Self::UmAlQura,
+            _ => Self::Custom(code),
+        }
+    }
+
+    /// The calendar a locale code asks for: the third byte of a hex code,
+    /// or the low byte of the hex number after the comma of a tag such as
+    /// `th-TH,107`, whose next byte picks the digits. `None` when the code
+    /// names no calendar.
+    pub fn from_locale_code(code: &str) -> Option<Self> {
+        let byte = match code.split_once(',') {
+            Some((_, suffix)) => u32::from_str_radix(suffix.trim(), 16).ok()? & 0xff,
+            None => {
+                let code = u32::from_str_radix(code, 16).ok()?;
+                if code & 0xff_0000 == 0 {
+                    return None;
+                }
+                (code >> 16) & 0xff
+            }
+        };
+        Some(Self::from_code(byte as u8))
+    }
+
+    /// Whether years are counted in eras, so `yyyy` shows the year of the
+    /// era rather than the Gregorian one.
+    pub fn uses_eras(self) -> bool {
+        matches!(
+            self,
+            Self::Japanese | Self::Taiwan | Self::Korean | Self::Thai
+        )
+    }
+
+    /// Whether dates count from the Hijri epoch.
+    pub fn is_hijri(self) -> bool {
+        matches!(self, Self::Hijri | Self::UmAlQura)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -174,7 +249,10 @@ pub enum DateTokenKind {
     YearShort,
     BuddhistYear,
     BuddhistYearShort,
+    /// `g`, `gg` or `ggg`: the era name, abbreviated to full by `width`.
     Era,
+    /// `e` or `ee`: the year within the era.
+    EraYear,
     Month,
     MonthName,
     MonthNameShort,
@@ -275,7 +353,8 @@ pub struct Section {
     pub condition: Option<Condition>,
     pub color: Option<Color>,
     pub locale: Option<String>,
-    pub db_num: Option<DbNumType>,
+    pub calendar: CalendarKind,
+    pub numerals: Option<NumeralTag>,
     pub parens: bool,
     pub generated: bool,
     pub pattern: String,
@@ -325,7 +404,8 @@ impl Section {
             condition: None,
             color: None,
             locale: None,
-            db_num: None,
+            calendar: CalendarKind::Gregorian,
+            numerals: None,
             parens: false,
             generated: false,
             pattern: String::new(),
@@ -1,11 +1,11 @@
 use std::cmp::max;
 
-use crate::constants::{DateUnits, EPOCH_1317};
+use crate::constants::{DateUnits, EPOCH_1317, EPOCH_UMALQURA};
 
 use super::error::ParseError;
 use super::model::{
-    Color, DateToken, DateTokenKind, DbNumType, NumberPart, NumberToken, Section, SectionToken,
-    StringRule, StringToken, Token, TokenKind, TokenValue,
+    CalendarKind, Color, DateToken, DateTokenKind, NumberPart, NumberToken, NumeralTag, Section,
+    SectionToken, StringRule, StringToken, Token, TokenKind, TokenValue,
 };
 
 pub struct SectionParseResult {
@@ -187,8 +187,10 @@ pub fn parse_format_section(input_tokens: &[Token]) -> Result<SectionParseResult
                 if !have_locale && let Some(value) = token_text(token) {
                     if value.eq_ignore_ascii_case("B2") {
                         section.date_system = EPOCH_1317;
+                        section.calendar = CalendarKind::Hijri;
                     } else {
                         section.date_system = crate::constants::EPOCH_1900;
+                        section.calendar = CalendarKind::Gregorian;
                     }
                 }
             }
@@ -237,23 +239,11 @@ pub fn parse_format_section(input_tokens: &[Token]) -> Result<SectionParseResult
             TokenKind::Skip | TokenKind::Fill => {
                 tokens.push(SectionToken::Token(token.clone()));
             }
-            TokenKind::DbNum => {
-                if let Some(value) = token_text(token) {
-                    let db_type = match value.to_ascii_lowercase().as_str() {
-                        "dbnum1" => Some(DbNumType::TradSimp),
-                        "dbnum2" => Some(DbNumType::TradFormal),
-                        "dbnum3" => Some(DbNumType::Simp),
-                        "dbnum4" => Some(DbNumType::FullWidth),
-                        _ => None,
-                    };
-                    if db_type.is_some() {
-                        section.db_num = db_type;
-                    }
+            TokenKind::DbNum | TokenKind::NatNum => {
+                if let Some(tag) = token_text(token).and_then(numeral_tag) {
+                    section.numerals = Some(tag);
                 }
             }
-            TokenKind::NatNum => {
-                // unsupported but tolerated
-            }
             TokenKind::Error => {
                 return Err(ParseError::new(format!(
                     "Illegal character: {}",
@@ -316,6 +306,18 @@ fn is_num_op_kind(kind: TokenKind, current: NumberPart) -> bool {
         || matches!(kind, TokenKind::Digit) && matches!(current, NumberPart::Denominator)
 }
 
+/// Reads `DBNum1` or `NatNum12`. Tags without a number are tolerated and
+/// ignored.
+fn numeral_tag(text: &str) -> Option<NumeralTag> {
+    let lower = text.to_ascii_lowercase();
+    if let Some(n) = lower.strip_prefix("dbnum") {
+        n.parse().ok().map(NumeralTag::DbNum)
+    } else {
+        let n = lower.strip_prefix("natnum")?;
+        n.parse().ok().map(NumeralTag::NatNum)
+    }
+}
+
 fn token_text(token: &Token) -> Option<&str> {
     match &token.value {
         TokenValue::Text(text) => Some(text.as_str()),
@@ -396,8 +398,9 @@ fn handle_datetime_token(
             dt.unit = DateUnits::YEAR;
         }
         'e' => {
-            dt.kind = DateTokenKind::Year;
+            dt.kind = DateTokenKind::EraYear;
             dt.unit = DateUnits::YEAR;
+            dt.zero_pad = value.len() >= 2;
         }
         'b' => {
             dt.unit = DateUnits::YEAR;
@@ -421,6 +424,7 @@ fn handle_datetime_token(
         'g' => {
             dt.unit = DateUnits::empty();
             dt.kind = DateTokenKind::Era;
+            dt.width = Some(value.len().min(3));
         }
         'h' => {
             dt.unit = DateUnits::HOUR;
@@ -519,12 +523,12 @@ fn handle_locale_token(token: &Token, section: &mut Section, tokens: &mut Vec<Se
         let code: String = parts.collect::<Vec<_>>().join("-");
         if !code.is_empty() {
             section.locale = Some(code.clone());
-            if let Ok(wincode) = i32::from_str_radix(&code, 16)
-                && (wincode & 0xff0000) != 0
-            {
-                let cal = (wincode >> 16) & 0xff;
-                if cal == 6 {
-                    section.date_system = EPOCH_1317;
+            if let Some(calendar) = CalendarKind::from_locale_code(&code) {
+                section.calendar = calendar;
+                match section.calendar {
+                    CalendarKind::UmAlQura => section.date_system = EPOCH_UMALQURA,
+                    CalendarKind::Hijri => section.date_system = EPOCH_1317,
+                    _ => {}
                 }
             }
         }
@@ -0,0 +1,241 @@
+//! Writing parsed patterns back out as canonical format codes.
+//!
+//! Equivalent spellings collapse to one code: digit groups are written as
+//! `#,##0`, redundant leading `#` placeholders are dropped, literal runs are
+//! merged and quoted the same way however they were escaped, and the
+//! sections and conditions the parser adds on its own are left out.
+
+use std::fmt::{self, Write};
+
+use crate::constants::EPOCH_1317;
+
+use super::model::{
+    CalendarKind, Color, ConditionOperator, DateToken, DateTokenKind, NumberPart, NumberToken,
+    NumeralTag, Pattern, Section, SectionToken, Token, TokenKind,
+};
+
+/// Characters that read back as literals without quoting or escaping.
+const BARE_LITERALS: &str = "$():!^&'~{}<>=";
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let conditional = is_conditional(self);
+        let mut first = true;
+        for section in self.partitions.iter().filter(|part| !part.generated) {
+            if !first {
+                f.write_char(';')?;
+            }
+            first = false;
+            write_section(f, section, conditional)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_section(f, self, true)
+    }
+}
+
+/// Patterns without conditions of their own get `[>0]` and `[<0]` attached
+/// to their first two sections, and never a leading volatile minus on the
+/// first; conditional patterns get one unless it only admits negatives.
+pub(super) fn is_conditional(pattern: &Pattern) -> bool {
+    let Some(first) = pattern.partitions.first() else {
+        return false;
+    };
+    let leading_minus = matches!(
+        first.tokens.first(),
+        Some(SectionToken::Token(tok)) if tok.kind == TokenKind::Minus && tok.volatile
+    );
+    let implicit = first
+        .condition
+        .as_ref()
+        .is_some_and(|cond| cond.operator == ConditionOperator::Greater && cond.operand == 0.0);
+    leading_minus || !implicit
+}
+
+fn write_section(f: &mut fmt::Formatter<'_>, section: &Section, condition: bool) -> fmt::Result {
+    if let Some(color) = &section.color {
+        f.write_str(&color_code(color))?;
+    }
+    if condition && let Some(cond) = &section.condition {
+        write!(f, "[{}{}]", cond.operator, cond.operand)?;
+    }
+    if let Some(code) = &section.locale {
+        write!(f, "[$-{code}]")?;
+    }
+    if section.date_system == EPOCH_1317 && !locale_sets_hijri(section) {
+        f.write_str("B2")?;
+    }
+    match section.numerals {
+        Some(NumeralTag::DbNum(n)) => write!(f, "[DBNum{n}]")?,
+        Some(NumeralTag::NatNum(n)) => write!(f, "[NatNum{n}]")?,
+        None => {}
+    }
+
+    let scale_commas = scale_commas(section);
+    let last_number = section
+        .tokens
+        .iter()
+        .rposition(|tok| matches!(tok, SectionToken::Number(_)));
+
+    let mut literal = String::new();
+    for (idx, token) in section.tokens.iter().enumerate() {
+        if let SectionToken::String(string) = token {
+            if section.percent && string.value == "%" {
+                write_literal(f, &literal, section)?;
+                literal.clear();
+                f.write_char('%')?;
+            } else {
+                literal.push_str(&string.value);
+            }
+            continue;
+        }
+        write_literal(f, &literal, section)?;
+        literal.clear();
+
+        match token {
+            SectionToken::Token(tok) => write_token(f, tok)?,
+            SectionToken::Number(number) => write_number(f, number, section)?,
+            SectionToken::Date(date) => write_date(f, date)?,
+            SectionToken::Div => f.write_char('/')?,
+            SectionToken::Exp { plus } => f.write_str(if *plus { "E+" } else { "E-" })?,
+            SectionToken::String(_) => {}
+        }
+        if Some(idx) == last_number {
+            for _ in 0..scale_commas {
+                f.write_char(',')?;
+            }
+        }
+    }
+    write_literal(f, &literal, section)
+}
+
+/// The bracketed code selecting `color`, such as `[Red]` or `[Color12]`.
+pub(super) fn color_code(color: &Color) -> String {
+    match color {
+        Color::Named(name) => format!("[{}]", capitalize(name)),
+        Color::Index(idx) => format!("[Color{idx}]"),
+    }
+}
+
+fn write_token(f: &mut fmt::Formatter<'_>, tok: &Token) -> fmt::Result {
+    match tok.kind {
+        TokenKind::Minus if tok.volatile => Ok(()),
+        TokenKind::Minus => f.write_char('-'),
+        TokenKind::Plus => f.write_char('+'),
+        TokenKind::Point => f.write_char('.'),
+        TokenKind::Space => f.write_char(' '),
+        TokenKind::Text => f.write_char('@'),
+        TokenKind::General => f.write_str("General"),
+        TokenKind::Ampm => f.write_str(if tok.short { "A/P" } else { "AM/PM" }),
+        TokenKind::Skip | TokenKind::Fill => f.write_str(&tok.raw),
+        _ => Ok(()),
+    }
+}
+
+fn write_number(
+    f: &mut fmt::Formatter<'_>,
+    number: &NumberToken,
+    section: &Section,
+) -> fmt::Result {
+    let single_integer = number.part == NumberPart::Integer
+        && section.int_pattern.len() == 1
+        && !section.exponential;
+    if !single_integer {
+        return f.write_str(&number.pattern);
+    }
+
+    // Leading "#" placeholders never print anything.
+    let trimmed = number.pattern.trim_start_matches('#');
+    let digits = if trimmed.is_empty() { "#" } else { trimmed };
+    if !section.grouping {
+        return f.write_str(digits);
+    }
+    let padded = format!("{digits:#>4}");
+    let split = padded.len() - 3;
+    write!(f, "{},{}", &padded[..split], &padded[split..])
+}
+
+fn write_date(f: &mut fmt::Formatter<'_>, date: &DateToken) -> fmt::Result {
+    let pad = |ch: &str| {
+        if date.zero_pad {
+            ch.repeat(2)
+        } else {
+            ch.to_string()
+        }
+    };
+    let elapsed = |ch: &str| format!("[{}]", ch.repeat(date.width.unwrap_or(1)));
+    let code = match date.kind {
+        DateTokenKind::Year => "yyyy".to_string(),
+        DateTokenKind::YearShort => "yy".to_string(),
+        DateTokenKind::BuddhistYear => "bbbb".to_string(),
+        DateTokenKind::BuddhistYearShort => "bb".to_string(),
+        DateTokenKind::Era => "g".repeat(date.width.unwrap_or(1)),
+        DateTokenKind::EraYear => pad("e"),
+        DateTokenKind::Month | DateTokenKind::Minute => pad("m"),
+        DateTokenKind::MonthName => "mmmm".to_string(),
+        DateTokenKind::MonthNameShort => "mmm".to_string(),
+        DateTokenKind::MonthNameSingle => "mmmmm".to_string(),
+        DateTokenKind::Weekday => "dddd".to_string(),
+        DateTokenKind::WeekdayShort => "ddd".to_string(),
+        DateTokenKind::Day => pad("d"),
+        DateTokenKind::Hour => pad("h"),
+        DateTokenKind::Second => pad("s"),
+        DateTokenKind::HourElapsed => elapsed("h"),
+        DateTokenKind::MinuteElapsed => elapsed("m"),
+        DateTokenKind::SecondElapsed => elapsed("s"),
+        DateTokenKind::Subsecond => format!(".{}", "0".repeat(date.decimals as usize)),
+    };
+    f.write_str(&code)
+}
+
+/// Writes a run of literal text: bare when every character reads back as a
+/// literal, escaped when it is a single character and quoted otherwise.
+fn write_literal(f: &mut fmt::Formatter<'_>, text: &str, section: &Section) -> fmt::Result {
+    if text.is_empty() {
+        return Ok(());
+    }
+    let date = !section.date.is_empty();
+    let bare = |ch: char| BARE_LITERALS.contains(ch) || (date && matches!(ch, '/' | ','));
+    if text.chars().all(bare) {
+        return f.write_str(text);
+    }
+    if text.chars().count() == 1 || text.contains('"') {
+        for ch in text.chars() {
+            if bare(ch) {
+                f.write_char(ch)?;
+            } else {
+                write!(f, "\\{ch}")?;
+            }
+        }
+        return Ok(());
+    }
+    write!(f, "\"{text}\"")
+}
+
+/// Each trailing comma divides by a thousand; percent overrides them.
+fn scale_commas(section: &Section) -> usize {
+    if section.percent || section.scale >= 1.0 || section.scale <= 0.0 {
+        return 0;
+    }
+    (section.scale.log10() / -3.0).round() as usize
+}
+
+fn locale_sets_hijri(section: &Section) -> bool {
+    section
+        .locale
+        .as_deref()
+        .and_then(CalendarKind::from_locale_code)
+        .is_some_and(CalendarKind::is_hijri)
+}
+
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
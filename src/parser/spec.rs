@@ -0,0 +1,303 @@
+//! Building format codes from typed settings, the way a "Format Cells"
+//! dialog does.
+
+use super::edit::NegativeStyle;
+use super::error::ParseError;
+use super::model::Color;
+use super::pattern::parse_pattern;
+use super::serialize::color_code;
+
+/// The family of format a [`NumberFormatSpec`] produces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NumberFormatKind {
+    Number,
+    Currency,
+    /// Currency with the symbol at the cell edge and zero shown as a dash.
+    /// Negatives go in parentheses with the symbol before the digits and
+    /// behind a minus with it after them.
+    Accounting,
+    Percent,
+    Scientific,
+    Fraction,
+    Date,
+    Time,
+}
+
+/// Where the currency symbol goes relative to the digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CurrencyPlacement {
+    /// `€1,234.10`
+    #[default]
+    Before,
+    /// `€ 1,234.10`
+    BeforeWithSpace,
+    /// `1,234.10€`
+    After,
+    /// `1,234.10 €`
+    AfterWithSpace,
+}
+
+/// Typed settings for a format code. A currency spec with `€`, locale
+/// `407` and [`NegativeStyle::RedMinus`] writes
+/// `[$€-407]#,##0.00;[Red]-[$€-407]#,##0.00`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberFormatSpec {
+    pub kind: NumberFormatKind,
+    /// Decimal places for numbers and subsecond digits for times. Fractions
+    /// take it as the number of denominator digits, from 1 to 3.
+    pub decimals: usize,
+    pub grouping: bool,
+    /// The currency symbol for currency and accounting formats, or `None`
+    /// for no symbol.
+    pub currency: Option<String>,
+    pub currency_placement: CurrencyPlacement,
+    /// Accounting formats ignore it and follow their currency placement,
+    /// as do dates and times.
+    pub negative: NegativeStyle,
+    /// Color for every section the negative style does not color itself.
+    pub color: Option<Color>,
+    /// Locale code written into the pattern, either a Windows LCID in hex
+    /// such as `407` or a tag such as `de-DE`.
+    pub locale: Option<String>,
+}
+
+impl NumberFormatSpec {
+    /// Starts from the settings Excel's dialog shows for `kind`: two
+    /// decimals, grouping for currency and accounting, `$` as the symbol.
+    pub fn new(kind: NumberFormatKind) -> Self {
+        let money = matches!(
+            kind,
+            NumberFormatKind::Currency | NumberFormatKind::Accounting
+        );
+        let decimals = match kind {
+            NumberFormatKind::Fraction => 1,
+            NumberFormatKind::Date | NumberFormatKind::Time => 0,
+            _ => 2,
+        };
+        Self {
+            kind,
+            decimals,
+            grouping: money,
+            currency: money.then(|| "$".to_string()),
+            currency_placement: CurrencyPlacement::default(),
+            negative: NegativeStyle::Minus,
+            color: None,
+            locale: None,
+        }
+    }
+
+    pub fn with_decimals(mut self, decimals: usize) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    pub fn with_grouping(mut self, grouping: bool) -> Self {
+        self.grouping = grouping;
+        self
+    }
+
+    pub fn with_currency(mut self, symbol: impl Into<String>) -> Self {
+        self.currency = Some(symbol.into());
+        self
+    }
+
+    pub fn without_currency(mut self) -> Self {
+        self.currency = None;
+        self
+    }
+
+    pub fn with_currency_placement(mut self, placement: CurrencyPlacement) -> Self {
+        self.currency_placement = placement;
+        self
+    }
+
+    pub fn with_negative_style(mut self, style: NegativeStyle) -> Self {
+        self.negative = style;
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Writes the format code, checked by parsing it back.
+    pub fn to_pattern(&self) -> Result<String, ParseError> {
+        let code = self.code();
+        parse_pattern(&code)?;
+        Ok(code)
+    }
+
+    fn code(&self) -> String {
+        match self.kind {
+            NumberFormatKind::Accounting => return self.accounting(),
+            NumberFormatKind::Date => return self.section("yyyy-mm-dd"),
+            NumberFormatKind::Time => {
+                let subsecond = self.decimals.min(3);
+                let time = if subsecond > 0 {
+                    format!("h:mm:ss.{}", "0".repeat(subsecond))
+                } else {
+                    "h:mm:ss".to_string()
+                };
+                return self.section(&time);
+            }
+            _ => {}
+        }
+
+        let body = match self.kind {
+            NumberFormatKind::Currency => {
+                let symbol = self.symbol();
+                match self.currency_placement {
+                    _ if symbol.is_empty() => self.digits(),
+                    CurrencyPlacement::Before => format!("{symbol}{}", self.digits()),
+                    CurrencyPlacement::BeforeWithSpace => format!("{symbol} {}", self.digits()),
+                    CurrencyPlacement::After => format!("{}{symbol}", self.digits()),
+                    CurrencyPlacement::AfterWithSpace => format!("{} {symbol}", self.digits()),
+                }
+            }
+            NumberFormatKind::Percent => format!("{}%", self.digits()),
+            NumberFormatKind::Scientific => format!("0{}E+00", self.fraction_digits()),
+            NumberFormatKind::Fraction => {
+                let width = "?".repeat(self.decimals.clamp(1, 3));
+                format!("# {width}/{width}")
+            }
+            _ => self.digits(),
+        };
+
+        let positive = self.section(&body);
+        let red = Some(Color::Named("red".to_string()));
+        match self.negative {
+            NegativeStyle::Minus => positive,
+            NegativeStyle::Parentheses => format!(
+                "{};{}",
+                self.section(&format!("{body}_)")),
+                self.section(&format!("({body})"))
+            ),
+            NegativeStyle::Red => {
+                format!("{positive};{}", self.colored(&body, red.as_ref()))
+            }
+            NegativeStyle::RedMinus => {
+                format!(
+                    "{positive};{}",
+                    self.colored(&format!("-{body}"), red.as_ref())
+                )
+            }
+            NegativeStyle::RedParentheses => format!(
+                "{};{}",
+                self.section(&format!("{body}_)")),
+                self.colored(&format!("({body})"), red.as_ref())
+            ),
+        }
+    }
+
+    /// Excel's accounting layout: the symbol and the digits are pushed to
+    /// opposite edges by a fill, and zero is a dash lined up with the
+    /// decimal point.
+    fn accounting(&self) -> String {
+        let symbol = self.symbol();
+        let digits = self.digits();
+        let dash = format!("\"-\"{}", "?".repeat(self.decimals));
+        let sections = match self.currency_placement {
+            CurrencyPlacement::Before | CurrencyPlacement::BeforeWithSpace => {
+                let space = match self.currency_placement {
+                    CurrencyPlacement::BeforeWithSpace if !symbol.is_empty() => " ",
+                    _ => "",
+                };
+                let lead = format!("_({symbol}{space}* ");
+                [
+                    format!("{lead}{digits}_)"),
+                    format!("{lead}({digits})"),
+                    format!("{lead}{dash}_)"),
+                    "_(@_)".to_string(),
+                ]
+            }
+            CurrencyPlacement::After | CurrencyPlacement::AfterWithSpace => {
+                let space = match self.currency_placement {
+                    CurrencyPlacement::AfterWithSpace if !symbol.is_empty() => " ",
+                    _ => "",
+                };
+                let tail = format!("{space}{symbol}_-");
+                [
+                    format!("_-* {digits}{tail}"),
+                    format!("-* {digits}{tail}"),
+                    format!("_-* {dash}{tail}"),
+                    "_-@_-".to_string(),
+                ]
+            }
+        };
+        sections
+            .iter()
+            .map(|body| self.section(body))
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+
+    /// Prefixes a section body with the spec's color and, unless the
+    /// currency tag already carries it, its locale.
+    fn section(&self, body: &str) -> String {
+        self.colored(body, self.color.as_ref())
+    }
+
+    fn colored(&self, body: &str, color: Option<&Color>) -> String {
+        let mut out = color.map(color_code).unwrap_or_default();
+        if let Some(locale) = &self.locale
+            && !self.tags_currency()
+        {
+            out.push_str(&format!("[$-{locale}]"));
+        }
+        out.push_str(body);
+        out
+    }
+
+    /// Integer digits with optional grouping, then the decimals.
+    fn digits(&self) -> String {
+        let integer = if self.grouping { "#,##0" } else { "0" };
+        format!("{integer}{}", self.fraction_digits())
+    }
+
+    fn fraction_digits(&self) -> String {
+        if self.decimals == 0 {
+            String::new()
+        } else {
+            format!(".{}", "0".repeat(self.decimals))
+        }
+    }
+
+    /// Whether the currency symbol is written as a `[$sym-locale]` tag.
+    fn tags_currency(&self) -> bool {
+        let money = matches!(
+            self.kind,
+            NumberFormatKind::Currency | NumberFormatKind::Accounting
+        );
+        money
+            && self.currency.as_deref().is_some_and(|symbol| {
+                !symbol.is_empty()
+                    && !symbol.contains(['-', ']', '"'])
+                    && (symbol != "$" || self.locale.is_some())
+            })
+    }
+
+    /// The currency symbol as it is written in the code: `$` on its own,
+    /// other symbols in a currency tag the way Excel writes them, and
+    /// symbols a tag cannot hold in quotes.
+    fn symbol(&self) -> String {
+        let Some(symbol) = self.currency.as_deref().filter(|s| !s.is_empty()) else {
+            return String::new();
+        };
+        if self.tags_currency() {
+            match &self.locale {
+                Some(locale) => format!("[${symbol}-{locale}]"),
+                None => format!("[${symbol}]"),
+            }
+        } else if symbol == "$" {
+            symbol.to_string()
+        } else {
+            format!("\"{}\"", symbol.replace('"', ""))
+        }
+    }
+}
@@ -78,7 +78,8 @@ pub fn typst_format_color(
 /// Typst entry point for the `getFormatInfo` function.
 /// Parse format pattern and return detailed information
 /// Args: format_string (bytes), currency_symbol (bytes, optional)
-/// Returns: JSON format parse result
+/// Returns: JSON with the pattern's category, decimals, grouping, scale,
+/// parentheses, date units, section colors and section tokens
 #[wasm_export(export_rename = "get-format-info")]
 pub fn typst_get_format_info(
     format_string_bytes: &[u8],
@@ -138,16 +139,24 @@ pub fn typst_get_format_info(
                 })
                 .collect();
 
-            SectionInfo {
-                index: i,
-                content: format!("{:?}", section),
-                tokens,
-            }
+            SectionInfo { index: i, tokens }
         })
         .collect();
 
+    let info = crate::parser::FormatInfo::from(&parsed);
     let response = ParseResponse {
         success: true,
+        category: info.category.as_str().to_string(),
+        max_decimals: info.max_decimals,
+        grouping: info.grouping,
+        scale: info.scale,
+        parentheses: info.parentheses,
+        date_units: info
+            .date_units
+            .iter_names()
+            .map(|(name, _)| name.to_ascii_lowercase())
+            .collect(),
+        colors: info.colors.iter().map(color_json).collect(),
         sections,
         error: None,
     };
@@ -182,6 +191,13 @@ pub fn typst_get_locale() -> Result<Vec<u8>, String> {
 #[derive(Serialize, Deserialize)]
 struct ParseResponse {
     success: bool,
+    category: String,
+    max_decimals: usize,
+    grouping: bool,
+    scale: f64,
+    parentheses: bool,
+    date_units: Vec<String>,
+    colors: Vec<Value>,
     sections: Vec<SectionInfo>,
     error: Option<String>,
 }
@@ -189,7 +205,6 @@ struct ParseResponse {
 #[derive(Serialize, Deserialize)]
 struct SectionInfo {
     index: usize,
-    content: String,
     tokens: Vec<TokenInfo>,
 }
 
@@ -204,6 +219,21 @@ struct LocaleResponse {
     locales: Vec<String>,
 }
 
+/// Section colors use the same JSON shape as `format-color`.
+fn color_json(color: &Option<crate::parser::Color>) -> Value {
+    match color {
+        Some(crate::parser::Color::Named(name)) => serde_json::json!({
+            "type": "string",
+            "value": name
+        }),
+        Some(crate::parser::Color::Index(idx)) => serde_json::json!({
+            "type": "index",
+            "value": idx
+        }),
+        None => Value::Null,
+    }
+}
+
 /// Parse formatter options
 /// If options is empty, return default options
 fn parse_formatter_options(options: &[u8]) -> Result<crate::FormatterOptions, String> {
@@ -269,6 +299,16 @@ fn parse_formatter_options(options: &[u8]) -> Result<crate::FormatterOptions, St
                         formatter_options.leap_1900 = b;
                     }
                 }
+                "time_zone" => {
+                    if let Some(zone) = value.as_str().and_then(crate::formatter::TimeZone::parse) {
+                        formatter_options.time_zone = Some(zone);
+                    }
+                }
+                "date_1904" => {
+                    if let Some(b) = value.as_bool() {
+                        formatter_options.date_1904 = b;
+                    }
+                }
                 "nbsp" => {
                     if let Some(b) = value.as_bool() {
                         formatter_options.nbsp = b;
@@ -293,10 +333,10 @@ fn parse_formatter_options(options: &[u8]) -> Result<crate::FormatterOptions, St
                     if let Some(arr) = value.as_array() {
                         let mut grouping = Vec::new();
                         for item in arr {
-                            if let Some(n) = item.as_u64() {
-                                if n <= u8::MAX as u64 {
-                                    grouping.push(n as u8);
-                                }
+                            if let Some(n) = item.as_u64()
+                                && n <= u8::MAX as u64
+                            {
+                                grouping.push(n as u8);
                             }
                         }
                         if !grouping.is_empty() {
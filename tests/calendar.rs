@@ -0,0 +1,417 @@
+use numfmt_rs::constants::{EPOCH_1317, EPOCH_UMALQURA};
+use numfmt_rs::formatter::serial::SerialError;
+use numfmt_rs::formatter::{CalendarError, FormatterError};
+use numfmt_rs::parser::CalendarKind;
+use numfmt_rs::{
+    Calendar, CalendarDay, CompiledFormat, DateValue, FormatterOptions, add_calendar, format,
+    format_with_options, parse_pattern, parse_with_pattern, remove_calendar,
+};
+
+fn ymd(year: i32, month: u8, day: u8) -> DateValue {
+    DateValue::new(year).with_month(month).with_day(day)
+}
+
+fn calendar_of(pattern: &str) -> CalendarKind {
+    parse_pattern(pattern).unwrap().partitions[0].calendar
+}
+
+#[test]
+fn reads_the_calendar_byte() {
+    assert_eq!(calendar_of("[$-409]yyyy"), CalendarKind::Gregorian);
+    assert_eq!(calendar_of("[$-1010409]yyyy"), CalendarKind::Gregorian);
+    assert_eq!(calendar_of("[$-2010409]yyyy"), CalendarKind::Gregorian);
+    assert_eq!(calendar_of("[$-30411]yyyy"), CalendarKind::Japanese);
+    assert_eq!(calendar_of("[$-40404]yyyy"), CalendarKind::Taiwan);
+    assert_eq!(calendar_of("[$-50412]yyyy"), CalendarKind::Korean);
+    assert_eq!(calendar_of("[$-60401]yyyy"), CalendarKind::Hijri);
+    assert_eq!(calendar_of("[$-7041E]yyyy"), CalendarKind::Thai);
+    assert_eq!(calendar_of("[$-8040D]yyyy"), CalendarKind::Hebrew);
+    assert_eq!(calendar_of("[$-170401]yyyy"), CalendarKind::UmAlQura);
+    assert_eq!(calendar_of("[$-A0401]yyyy"), CalendarKind::Gregorian);
+    assert_eq!(calendar_of("B2yyyy"), CalendarKind::Hijri);
+}
+
+#[test]
+fn counts_years_in_the_calendar_era() {
+    let date = ymd(2024, 3, 4);
+    assert_eq!(format("[$-30411]yyyy/m/d", date.clone()).unwrap(), "6/3/4");
+    assert_eq!(
+        format("[$-30411]ggge\"年\"", date.clone()).unwrap(),
+        "令和6年"
+    );
+    assert_eq!(
+        format("[$-40404]yyyy/mm/dd", date.clone()).unwrap(),
+        "113/03/04"
+    );
+    assert_eq!(
+        format("[$-50412]yyyy-mm-dd", date.clone()).unwrap(),
+        "4357-03-04"
+    );
+    assert_eq!(
+        format("[$-7041E]d/m/yyyy", date.clone()).unwrap(),
+        "4/3/2567"
+    );
+    assert_eq!(format("[$-7041E]d/m/yy", date.clone()).unwrap(), "4/3/67");
+    // The calendar's eras win over the locale's.
+    assert_eq!(format("[$-40411]ggge", date).unwrap(), "中華民國113");
+}
+
+#[test]
+fn converts_to_the_hebrew_calendar() {
+    // Month numbers count from Tishri, with Adar I and II as 6 and 7 in
+    // leap years.
+    let cases = [
+        ((2024, 3, 4), r#"כ"ד/ו'/תשפ"ד"#),  // 24 Adar I 5784
+        ((2024, 3, 31), r#"כ"א/ז'/תשפ"ד"#), // 21 Adar II 5784
+        ((2023, 9, 16), r#"א'/א'/תשפ"ד"#),  // Rosh Hashanah
+        ((2023, 9, 15), r#"כ"ט/י"ב/תשפ"ג"#),
+        ((2023, 3, 1), r#"ח'/ו'/תשפ"ג"#), // Adar in a common year
+        ((2000, 1, 1), r#"כ"ג/ד'/תש"ס"#),
+    ];
+    for ((y, m, d), expected) in cases {
+        assert_eq!(
+            format("[$-8040D]d/m/yyyy", ymd(y, m, d)).unwrap(),
+            expected,
+            "{y}-{m}-{d}"
+        );
+    }
+}
+
+#[test]
+fn writes_hebrew_dates_in_letters() {
+    let cases = [
+        ((2024, 3, 4), r#"כ"ד אדר א תשפ"ד"#),
+        ((2024, 3, 31), r#"כ"א אדר ב תשפ"ד"#),
+        ((2023, 3, 1), r#"ח' אדר תשפ"ג"#),
+        ((2024, 1, 25), r#"ט"ו שבט תשפ"ד"#),
+        ((2024, 4, 24), r#"ט"ז ניסן תשפ"ד"#),
+        ((2023, 9, 15), r#"כ"ט אלול תשפ"ג"#),
+    ];
+    for ((y, m, d), expected) in cases {
+        assert_eq!(
+            format("[$-8040D]d mmmm yyyy", ymd(y, m, d)).unwrap(),
+            expected,
+            "{y}-{m}-{d}"
+        );
+    }
+    // Thousands drop out of years and 500 to 900 stack tavs.
+    assert_eq!(format("[$-8040D]yyyy", ymd(1740, 3, 1)).unwrap(), r#"ת"ק"#);
+    let options = FormatterOptions::default().with_calendar(CalendarKind::Hebrew);
+    assert_eq!(
+        format_with_options("mmm d", ymd(2024, 3, 4), options).unwrap(),
+        r#"אדר א כ"ד"#
+    );
+}
+
+#[test]
+fn reads_era_years_back() {
+    let options = FormatterOptions::default();
+    let serial = ymd(2024, 3, 4).to_serial(1, true).unwrap();
+    let thai = parse_pattern("[$-7041E]d/m/yyyy").unwrap();
+    assert_eq!(
+        parse_with_pattern("4/3/2567", &thai, &options),
+        Some(serial)
+    );
+    let taiwan = parse_pattern("[$-40404]yyyy/mm/dd").unwrap();
+    assert_eq!(
+        parse_with_pattern("113/03/04", &taiwan, &options),
+        Some(serial)
+    );
+}
+
+#[test]
+fn hijri_serial_60_has_no_date() {
+    assert_eq!(format("B2yyyy-mm-dd", 59).unwrap(), "1317-10-28");
+    assert_eq!(format("B2yyyy-mm-dd", 60).unwrap(), "######");
+    assert_eq!(format("B2yyyy-mm-dd", 61).unwrap(), "1317-10-29");
+    let options = FormatterOptions {
+        invalid: "#VALUE!".to_string(),
+        ..FormatterOptions::default()
+    };
+    assert_eq!(
+        format_with_options("[$-60401]yyyy-mm-dd", 60.25, options.clone()).unwrap(),
+        "#VALUE!"
+    );
+    let options = FormatterOptions {
+        date_error_throws: true,
+        ..options
+    };
+    assert!(matches!(
+        format_with_options("B2yyyy-mm-dd", 60, options),
+        Err(FormatterError::InvalidDate)
+    ));
+}
+
+#[test]
+fn tabular_hijri_dates() {
+    let cases = [
+        (45_361, "1445-09-01"),
+        (45_362, "1445-09-02"),
+        (45_480, "1446-01-01"),
+        (2_958_465, "9666-04-03"),
+    ];
+    for (serial, expected) in cases {
+        assert_eq!(
+            format("B2yyyy-mm-dd", serial).unwrap(),
+            expected,
+            "{serial}"
+        );
+    }
+}
+
+#[test]
+fn umm_al_qura_dates() {
+    let cases = [
+        (121, "1318-01-01"),    // 1900-04-30
+        (45_362, "1445-09-01"), // 2024-03-11
+        (45_392, "1445-10-01"), // 2024-04-10
+        (45_480, "1446-01-01"), // 2024-07-07
+        (64_970, "1500-12-30"), // 2077-11-16
+    ];
+    for (serial, expected) in cases {
+        assert_eq!(
+            format("[$-170401]yyyy-mm-dd", serial).unwrap(),
+            expected,
+            "{serial}"
+        );
+    }
+    // The table covers AH 1318 to 1500 only.
+    assert_eq!(format("[$-170401]yyyy-mm-dd", 120).unwrap(), "######");
+    assert_eq!(format("[$-170401]yyyy-mm-dd", 64_971).unwrap(), "######");
+    // The tag survives a round trip and `B2` still means the tabular one.
+    let pattern = parse_pattern("[$-170401]yyyy").unwrap();
+    assert_eq!(pattern.to_string(), "[$-170401]yyyy");
+    assert_eq!(pattern.partitions[0].date_system, EPOCH_UMALQURA);
+    assert_eq!(
+        parse_pattern("B2yyyy").unwrap().partitions[0].date_system,
+        EPOCH_1317
+    );
+}
+
+#[test]
+fn hijri_serials_round_trip() {
+    let hijri = |y, m, d| DateValue::new(y).with_month(m).with_day(d);
+    assert_eq!(
+        hijri(1445, 9, 1).to_serial(EPOCH_UMALQURA, true),
+        Ok(45_362.0)
+    );
+    assert_eq!(hijri(1445, 9, 1).to_serial(EPOCH_1317, true), Ok(45_361.0));
+    assert_eq!(
+        hijri(1445, 10, 30).to_serial(EPOCH_UMALQURA, true),
+        Err(SerialError::InvalidDate)
+    );
+    assert_eq!(
+        hijri(1501, 1, 1).to_serial(EPOCH_UMALQURA, true),
+        Err(SerialError::InvalidDate)
+    );
+    for serial in [121.0, 45_392.5, 64_970.0] {
+        let date = DateValue::from_serial(serial, EPOCH_UMALQURA, true).unwrap();
+        assert_eq!(date.to_serial(EPOCH_UMALQURA, true), Ok(serial));
+    }
+    assert_eq!(
+        DateValue::from_serial(60.0, EPOCH_UMALQURA, true),
+        Err(SerialError::InvalidDate)
+    );
+}
+
+#[test]
+fn reads_the_calendar_after_a_tag() {
+    assert_eq!(calendar_of("[$-th-TH,107]yyyy"), CalendarKind::Thai);
+    assert_eq!(calendar_of("[$-zh-TW,4]yyyy"), CalendarKind::Taiwan);
+    assert_eq!(calendar_of("[$-ko-KR,5]yyyy"), CalendarKind::Korean);
+    assert_eq!(calendar_of("[$-411,3]yyyy"), CalendarKind::Japanese);
+    assert_eq!(calendar_of("[$-th-TH,101]yyyy"), CalendarKind::Gregorian);
+    assert_eq!(CalendarKind::from_locale_code("th-TH"), None);
+    assert_eq!(CalendarKind::from_locale_code("409"), None);
+
+    let date = ymd(2024, 3, 4);
+    assert_eq!(
+        format("[$-th-TH,107]d mmmm yyyy", date.clone()).unwrap(),
+        "4 มีนาคม 2567"
+    );
+    assert_eq!(
+        format("[$-zh-TW,4]yyyy/m/d", date.clone()).unwrap(),
+        "113/3/4"
+    );
+    assert_eq!(format("[$-411,3]ggge", date).unwrap(), "令和6");
+    assert_eq!(
+        parse_pattern("[$-th-TH,107]yyyy").unwrap().to_string(),
+        "[$-th-TH,107]yyyy"
+    );
+}
+
+#[test]
+fn calendar_option_applies_to_gregorian_sections() {
+    let date = ymd(2024, 3, 4);
+    let thai = FormatterOptions::default().with_calendar(CalendarKind::Thai);
+    assert_eq!(
+        format_with_options("d/m/yyyy", date.clone(), thai.clone()).unwrap(),
+        "4/3/2567"
+    );
+    assert_eq!(
+        format_with_options("yy e", date.clone(), thai.clone()).unwrap(),
+        "67 2567"
+    );
+    // `bbbb` counts Buddhist years whatever the calendar.
+    assert_eq!(
+        format_with_options("bbbb", date.clone(), thai.clone()).unwrap(),
+        "2567"
+    );
+    // Codes that pick a calendar keep it.
+    assert_eq!(
+        format_with_options("[$-30411]yyyy", date.clone(), thai.clone()).unwrap(),
+        "6"
+    );
+    assert_eq!(
+        format_with_options("B2yyyy-mm-dd", 45_361, thai.clone()).unwrap(),
+        "1445-09-01"
+    );
+
+    let serial = date.to_serial(1, true).unwrap();
+    let pattern = parse_pattern("d/m/yyyy").unwrap();
+    assert_eq!(
+        parse_with_pattern("4/3/2567", &pattern, &thai),
+        Some(serial)
+    );
+
+    let hijri = FormatterOptions::default().with_calendar(CalendarKind::UmAlQura);
+    assert_eq!(
+        format_with_options("yyyy-mm-dd", 45_362, hijri).unwrap(),
+        "1445-09-01"
+    );
+    let taiwan = FormatterOptions::default().with_calendar(CalendarKind::Taiwan);
+    assert_eq!(
+        format_with_options("yyyy/mm/dd", date, taiwan).unwrap(),
+        "113/03/04"
+    );
+}
+
+/// A retail calendar whose fiscal 2024 opens on Sunday 2024-02-04, with
+/// 52-week years split into 4-4-5 week periods.
+struct Retail445;
+
+impl Calendar for Retail445 {
+    fn day(&self, serial: i64) -> Option<CalendarDay> {
+        let offset = serial - 45_326;
+        if offset < 0 {
+            return None;
+        }
+        let in_year = offset % 364;
+        let week = in_year / 7;
+        let quarter = week / 13;
+        let period = match week % 13 {
+            0..=3 => 0,
+            4..=7 => 1,
+            _ => 2,
+        };
+        let first_week = quarter * 13 + [0, 4, 8][period as usize];
+        Some(CalendarDay {
+            year: 2024 + (offset / 364) as i32,
+            month: (quarter * 3 + period + 1) as u8,
+            day: (in_year - first_week * 7 + 1) as u8,
+            weekday: ((serial + 6) % 7) as u8,
+        })
+    }
+
+    fn month_name(&self, _year: i32, month: u8, short: bool) -> Option<String> {
+        Some(if short {
+            format!("P{month}")
+        } else {
+            format!("Period {month}")
+        })
+    }
+
+    fn era_name(&self, _day: &CalendarDay, _width: usize) -> Option<String> {
+        Some("FY".to_string())
+    }
+}
+
+#[test]
+fn custom_calendars_show_their_own_fields() {
+    assert_eq!(add_calendar(0x40, Retail445), Ok(()));
+    let cases = [
+        (45_326, "FY2024 P1 01 Sun"),
+        (45_354, "FY2024 P2 01 Sun"),
+        (45_382, "FY2024 P3 01 Sun"),
+        (45_416, "FY2024 P3 35 Sat"),
+        (45_417, "FY2024 P4 01 Sun"),
+        (45_689, "FY2024 P12 35 Sat"),
+        (45_690, "FY2025 P1 01 Sun"),
+    ];
+    for (serial, expected) in cases {
+        assert_eq!(
+            format("[$-400409]gyyyy mmm dd ddd", serial).unwrap(),
+            expected,
+            "{serial}"
+        );
+    }
+    assert_eq!(
+        format("[$-en-US,40]mmmm/d/yy", 45_417.5).unwrap(),
+        "Period 4/1/24"
+    );
+    let options = FormatterOptions::default().with_calendar(CalendarKind::Custom(0x40));
+    assert_eq!(
+        format_with_options("yyyy-mm-dd hh:mm", 45_417.5, options.clone()).unwrap(),
+        "2024-04-01 12:00"
+    );
+    // Days before the calendar starts have no date.
+    assert_eq!(
+        format_with_options("yyyy-mm-dd", 45_000, options).unwrap(),
+        "######"
+    );
+    // Fiscal dates are not read back as Gregorian ones.
+    let pattern = parse_pattern("[$-400409]yyyy-mm-dd").unwrap();
+    let defaults = FormatterOptions::default();
+    assert_eq!(parse_with_pattern("2024-04-01", &pattern, &defaults), None);
+    assert!(remove_calendar(0x40));
+    assert!(!remove_calendar(0x40));
+}
+
+#[test]
+fn unregistered_calendar_codes_show_gregorian_dates() {
+    assert_eq!(calendar_of("[$-410409]yyyy"), CalendarKind::Custom(0x41));
+    assert_eq!(
+        format("[$-410409]yyyy-mm-dd", 45_417).unwrap(),
+        "2024-05-05"
+    );
+    assert_eq!(
+        add_calendar(0x07, Retail445),
+        Err(CalendarError::BuiltIn(0x07))
+    );
+    assert_eq!(
+        add_calendar(0x01, Retail445),
+        Err(CalendarError::BuiltIn(0x01))
+    );
+}
+
+#[test]
+fn calendars_can_be_given_without_a_code() {
+    let options = FormatterOptions::default().with_custom_calendar(Retail445);
+    assert_eq!(
+        format_with_options("gyyyy mmm dd", 45_417, options.clone()).unwrap(),
+        "FY2024 P4 01"
+    );
+    // It stands in for `calendar`, but codes that pick one keep it.
+    let options = options.with_calendar(CalendarKind::Thai);
+    assert_eq!(
+        format_with_options("yyyy mmm", 45_417, options.clone()).unwrap(),
+        "2024 P4"
+    );
+    assert_eq!(
+        format_with_options("[$-8040D]d mmmm", 45_417, options).unwrap(),
+        "כ\"ז ניסן"
+    );
+}
+
+#[test]
+fn compiled_formats_keep_the_calendar_they_resolved() {
+    assert_eq!(add_calendar(0x42, Retail445), Ok(()));
+    let compiled =
+        CompiledFormat::new("[$-420409]yyyy mmm dd", FormatterOptions::default()).unwrap();
+    assert!(remove_calendar(0x42));
+    assert_eq!(compiled.format(45_417).unwrap(), "2024 P4 01");
+    assert_eq!(
+        format("[$-420409]yyyy mmm dd", 45_417).unwrap(),
+        "2024 May 05"
+    );
+}
@@ -0,0 +1,83 @@
+use std::sync::Arc;
+use std::thread;
+
+use numfmt_rs::{
+    ColorValue, CompiledFormat, FormatterError, FormatterOptions, format_color, format_with_options,
+};
+
+#[test]
+fn formats_like_format_with_options() {
+    let options = FormatterOptions::default().with_locale("de");
+    let codes = [
+        "#,##0.00;[Red](#,##0.00);\"zero\"",
+        "0.0%",
+        "dd.mm.yyyy hh:mm",
+        "[$€-407]#,##0.00",
+        "General",
+        "@\" units\"",
+    ];
+    for code in codes {
+        let compiled = CompiledFormat::new(code, options.clone()).unwrap();
+        for value in [0.0, 1234.5, -98765.25, 45355.75] {
+            assert_eq!(
+                compiled.format(value).unwrap(),
+                format_with_options(code, value, options.clone()).unwrap(),
+                "{code} with {value}"
+            );
+        }
+        assert_eq!(
+            compiled.format("text").unwrap(),
+            format_with_options(code, "text", options.clone()).unwrap()
+        );
+    }
+}
+
+#[test]
+fn reports_colors() {
+    let compiled =
+        CompiledFormat::new("[Blue]0;[Red]-0;[Color10]0", FormatterOptions::default()).unwrap();
+    assert_eq!(
+        compiled.color(-1.0),
+        Some(ColorValue::String("red".to_string()))
+    );
+    assert_eq!(
+        compiled.color(0.0),
+        format_color(
+            "[Blue]0;[Red]-0;[Color10]0",
+            0.0,
+            FormatterOptions::default()
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn handles_invalid_patterns_per_options() {
+    assert!(matches!(
+        CompiledFormat::new("0;0;0;0;0", FormatterOptions::default()),
+        Err(FormatterError::Parse(_))
+    ));
+
+    let options = FormatterOptions {
+        throws: false,
+        ..FormatterOptions::default()
+    };
+    let compiled = CompiledFormat::new("0;0;0;0;0", options.clone()).unwrap();
+    assert_eq!(
+        compiled.format(1.0).unwrap(),
+        format_with_options("0;0;0;0;0", 1.0, options).unwrap()
+    );
+}
+
+#[test]
+fn shares_across_threads() {
+    let compiled = Arc::new(CompiledFormat::new("#,##0.00", FormatterOptions::default()).unwrap());
+    let handles: Vec<_> = (0..4)
+        .map(|n| {
+            let compiled = Arc::clone(&compiled);
+            thread::spawn(move || compiled.format(f64::from(n) * 1000.0).unwrap())
+        })
+        .collect();
+    let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert_eq!(results, ["0.00", "1,000.00", "2,000.00", "3,000.00"]);
+}
@@ -0,0 +1,100 @@
+use numfmt_rs::{
+    DateValue, FormatterError, FormatterOptions, format_with_options, parse_date, parse_pattern,
+    parse_with_pattern,
+};
+
+fn mac() -> FormatterOptions {
+    FormatterOptions::default().with_date_1904(true)
+}
+
+fn render(pattern: &str, value: f64) -> String {
+    format_with_options(pattern, value, mac()).unwrap()
+}
+
+#[test]
+fn counts_serials_from_1904() {
+    assert_eq!(render("yyyy-mm-dd dddd", 0.0), "1904-01-01 Friday");
+    assert_eq!(render("yyyy-mm-dd", 43830.0), "2024-01-01");
+    assert_eq!(render("d mmm yyyy h:mm", 1.5), "2 Jan 1904 12:00");
+    // No phantom leap day: serial 60 is an ordinary date.
+    assert_eq!(render("yyyy-mm-dd", 59.0), "1904-02-29");
+    assert_eq!(
+        format_with_options("yyyy-mm-dd", 43830.0, FormatterOptions::default()).unwrap(),
+        "2019-12-31"
+    );
+}
+
+#[test]
+fn shows_negative_dates_and_times_with_a_minus() {
+    assert_eq!(render("h:mm:ss", -0.25), "-6:00:00");
+    assert_eq!(render("yyyy-mm-dd", -1.0), "-1904-01-02");
+    assert_eq!(render("m/d/yyyy h:mm", -1.25), "-1/2/1904 6:00");
+    assert_eq!(render("[h]:mm", -1.25), "-30:00");
+    assert_eq!(render("0.00", -1.0), "-1.00");
+}
+
+#[test]
+fn checks_bounds_on_the_1904_calendar() {
+    let options = FormatterOptions {
+        date_span_large: false,
+        ..mac()
+    };
+    let max = 2_958_466.0 - 1_462.0;
+    assert_eq!(
+        format_with_options("yyyy-mm-dd", max - 1.0, options.clone()).unwrap(),
+        "9999-12-31"
+    );
+    assert_eq!(
+        format_with_options("yyyy-mm-dd", max, options).unwrap(),
+        "2957004"
+    );
+}
+
+#[test]
+fn converts_dates_to_1904_serials() {
+    let date = DateValue::new(2024)
+        .with_month(1)
+        .with_day(1)
+        .with_time(12, 0, 0);
+    assert_eq!(
+        format_with_options("0.00", date.clone(), mac()).unwrap(),
+        "43830.50"
+    );
+    assert_eq!(
+        format_with_options("yyyy-mm-dd h:mm", date, mac()).unwrap(),
+        "2024-01-01 12:00"
+    );
+
+    let (_, serial, _) = parse_date("2024-01-01", &mac()).unwrap();
+    assert_eq!(serial, 43830.0);
+    assert!(parse_date("1900-02-29", &mac()).is_none());
+
+    let pattern = parse_pattern("yyyy-mm-dd").unwrap();
+    assert_eq!(
+        parse_with_pattern("1904-01-02", &pattern, &mac()),
+        Some(1.0)
+    );
+}
+
+#[test]
+fn dates_before_1904_are_out_of_bounds() {
+    let date = DateValue::new(1903).with_month(12).with_day(31);
+    assert_eq!(
+        format_with_options("yyyy-mm-dd", date.clone(), mac()).unwrap(),
+        "######"
+    );
+    let throwing = FormatterOptions {
+        date_error_throws: true,
+        ..mac()
+    };
+    assert!(matches!(
+        format_with_options("yyyy-mm-dd", date, throwing),
+        Err(FormatterError::DateOutOfBounds)
+    ));
+    let first = DateValue::new(1904).with_month(1).with_day(1);
+    assert_eq!(
+        format_with_options("yyyy-mm-dd", first, mac()).unwrap(),
+        "1904-01-01"
+    );
+    assert!(parse_date("1903-12-31", &mac()).is_none());
+}
@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+use numfmt_rs::{DurationValue, FormatterOptions, format, format_color, format_with_options};
+
+#[test]
+fn formats_elapsed_tokens() {
+    let span = Duration::new(3 * 86_400 + 4 * 3_600 + 5 * 60 + 6, 789_000_000);
+    assert_eq!(format("[h]:mm:ss.000", span).unwrap(), "76:05:06.789");
+    assert_eq!(format("[mm]:ss.00", span).unwrap(), "4565:06.79");
+    assert_eq!(format("[ss]", span).unwrap(), "273907");
+    assert_eq!(format("d \"days\" hh:mm", span).unwrap(), "3 days 04:05");
+}
+
+#[test]
+fn keeps_millisecond_precision_on_long_spans() {
+    // Twenty years of runtime plus a single millisecond.
+    let span = Duration::new(20 * 365 * 86_400, 1_000_000);
+    assert_eq!(format("[h]:mm:ss.000", span).unwrap(), "175200:00:00.001");
+    let just_under = Duration::new(20 * 365 * 86_400 - 1, 999_000_000);
+    assert_eq!(
+        format("[h]:mm:ss.000", just_under).unwrap(),
+        "175199:59:59.999"
+    );
+}
+
+#[test]
+fn rounds_subseconds_the_way_numbers_do() {
+    let span = Duration::from_millis(59_600);
+    assert_eq!(format("[mm]:ss", span).unwrap(), "01:00");
+    assert_eq!(format("[mm]:ss.0", span).unwrap(), "00:59.6");
+}
+
+#[test]
+fn negative_durations_overflow_in_the_1900_system() {
+    let span = -DurationValue::new(Duration::from_secs(90 * 60));
+    assert_eq!(format("[h]:mm", span).unwrap(), "######");
+    let options = FormatterOptions::default().with_date_1904(true);
+    assert_eq!(
+        format_with_options("[h]:mm", span, options).unwrap(),
+        "-1:30"
+    );
+    // Outside date sections the value is a plain number of days.
+    assert_eq!(format("0.0000", span).unwrap(), "-0.0625");
+}
+
+#[test]
+fn picks_sections_and_colors_by_sign() {
+    let span = DurationValue::from_secs_nanos(-30, -500_000_000);
+    assert_eq!(span.duration, Duration::new(30, 500_000_000));
+    assert!(span.negative);
+    assert_eq!(format("[ss];\"late\"", span).unwrap(), "late");
+    let pattern = "[Green][ss];[Red][ss]";
+    assert_eq!(
+        format_color(pattern, span, FormatterOptions::default()).unwrap(),
+        format_color(pattern, -1.0, FormatterOptions::default()).unwrap()
+    );
+}
@@ -0,0 +1,90 @@
+use numfmt_rs::{
+    DateValue, Era, EraTable, FormatterOptions, format, format_with_options, parse_pattern,
+    parse_with_pattern,
+};
+
+const JAPANESE_DATE: &str = "[$-411]ggge\"年\"m\"月\"d\"日\"";
+
+fn ymd(year: i32, month: u8, day: u8) -> DateValue {
+    DateValue::new(year).with_month(month).with_day(day)
+}
+
+#[test]
+fn writes_japanese_era_dates() {
+    assert_eq!(
+        format(JAPANESE_DATE, ymd(2024, 3, 4)).unwrap(),
+        "令和6年3月4日"
+    );
+    assert_eq!(format("[$-ja-JP]gge", ymd(2024, 3, 4)).unwrap(), "令6");
+    assert_eq!(format("[$-411]gee", ymd(2024, 3, 4)).unwrap(), "R06");
+
+    let options = FormatterOptions::default().with_locale("ja-JP");
+    assert_eq!(
+        format_with_options("ggge", ymd(1995, 6, 1), options).unwrap(),
+        "平成7"
+    );
+}
+
+#[test]
+fn switches_eras_on_their_first_day() {
+    let cases = [
+        ((1912, 7, 29), "明治45"),
+        ((1912, 7, 30), "大正1"),
+        ((1926, 12, 24), "大正15"),
+        ((1926, 12, 25), "昭和1"),
+        ((1989, 1, 7), "昭和64"),
+        ((1989, 1, 8), "平成1"),
+        ((2019, 4, 30), "平成31"),
+        ((2019, 5, 1), "令和1"),
+    ];
+    for ((y, m, d), expected) in cases {
+        assert_eq!(format("[$-411]ggge", ymd(y, m, d)).unwrap(), expected);
+    }
+}
+
+#[test]
+fn counts_minguo_years_in_taiwan() {
+    assert_eq!(
+        format("[$-zh-TW]ggge\"年\"", ymd(2024, 3, 4)).unwrap(),
+        "中華民國113年"
+    );
+    assert_eq!(format("[$-404]gge", ymd(1912, 1, 1)).unwrap(), "民國1");
+}
+
+#[test]
+fn takes_an_era_table_from_the_options() {
+    let table = EraTable::new([
+        Era::new("Second", "II", "B", (2000, 1, 1)),
+        Era::new("First", "I", "A", (1900, 1, 1)),
+    ]);
+    assert_eq!(table.eras()[0].name, "First");
+    let options = FormatterOptions::default().with_era_table(table);
+    assert_eq!(
+        format_with_options("ggg e / gg ee", ymd(2024, 3, 4), options).unwrap(),
+        "Second 25 / II 25"
+    );
+}
+
+#[test]
+fn keeps_the_common_era_without_an_era_table() {
+    assert_eq!(format("ge", ymd(2024, 3, 4)).unwrap(), "2024");
+    // Dates before the first era fall back the same way.
+    assert_eq!(format("[$-411]ggge", ymd(1700, 1, 1)).unwrap(), "1700");
+}
+
+#[test]
+fn reads_era_dates_back() {
+    let pattern = parse_pattern(JAPANESE_DATE).unwrap();
+    let options = FormatterOptions::default();
+    let serial = ymd(2024, 3, 4).to_serial(1, true).unwrap();
+    assert_eq!(
+        parse_with_pattern("令和6年3月4日", &pattern, &options),
+        Some(serial)
+    );
+    // Reiwa started in May 2019, so this day was still Heisei.
+    assert_eq!(
+        parse_with_pattern("令和1年4月1日", &pattern, &options),
+        None
+    );
+    assert_eq!(pattern.to_string(), "[$-411]ggge\\年m\\月d\\日");
+}
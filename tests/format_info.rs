@@ -0,0 +1,47 @@
+use numfmt_rs::constants::DateUnits;
+use numfmt_rs::parser::Color;
+use numfmt_rs::{FormatCategory, format_info};
+
+fn category(pattern: &str) -> FormatCategory {
+    format_info(pattern).unwrap().category
+}
+
+#[test]
+fn categorizes_patterns() {
+    assert_eq!(category("General"), FormatCategory::General);
+    assert_eq!(category("0.00"), FormatCategory::Number);
+    assert_eq!(category("$#,##0.00"), FormatCategory::Currency);
+    assert_eq!(category("[$€-407] #,##0"), FormatCategory::Currency);
+    assert_eq!(category("0%"), FormatCategory::Percent);
+    assert_eq!(category("# ?/?"), FormatCategory::Fraction);
+    assert_eq!(category("0.00E+00"), FormatCategory::Scientific);
+    assert_eq!(category("yyyy-mm-dd"), FormatCategory::Date);
+    assert_eq!(category("h:mm:ss"), FormatCategory::Time);
+    assert_eq!(category("[h]:mm"), FormatCategory::Time);
+    assert_eq!(category("m/d/yyyy h:mm"), FormatCategory::DateTime);
+    assert_eq!(category("@"), FormatCategory::Text);
+    assert!(format_info("0;0;0;0;0").is_err());
+}
+
+#[test]
+fn reports_section_details() {
+    let info = format_info("[Green]#,##0.000_);[Red](#,##0.000)").unwrap();
+    assert_eq!(info.max_decimals, 3);
+    assert!(info.grouping);
+    assert!(info.parentheses);
+    assert_eq!(info.scale, 1.0);
+    assert_eq!(info.colors[0], Some(Color::Named("green".into())));
+    assert_eq!(info.colors[1], Some(Color::Named("red".into())));
+
+    let info = format_info("0.0%").unwrap();
+    assert_eq!((info.max_decimals, info.scale), (1, 100.0));
+    assert!(!info.parentheses);
+    assert_eq!(format_info("0,,").unwrap().scale, 0.001_f64.powi(2));
+
+    let info = format_info("yyyy-mm-dd hh:mm:ss.00").unwrap();
+    assert_eq!(info.max_decimals, 2);
+    assert!(
+        info.date_units
+            .contains(DateUnits::YEAR | DateUnits::SECOND)
+    );
+}
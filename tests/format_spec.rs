@@ -0,0 +1,176 @@
+use numfmt_rs::parser::Color;
+use numfmt_rs::{
+    CurrencyPlacement, FormatterOptions, NegativeStyle, NumberFormatKind, NumberFormatSpec,
+    format_with_options,
+};
+
+fn spec(kind: NumberFormatKind) -> NumberFormatSpec {
+    NumberFormatSpec::new(kind)
+}
+
+fn render(code: &str, value: f64) -> String {
+    format_with_options(code, value, FormatterOptions::default()).unwrap()
+}
+
+#[test]
+fn writes_number_formats() {
+    let code = |s: NumberFormatSpec| s.to_pattern().unwrap();
+    assert_eq!(code(spec(NumberFormatKind::Number)), "0.00");
+    assert_eq!(
+        code(
+            spec(NumberFormatKind::Number)
+                .with_decimals(0)
+                .with_grouping(true)
+                .with_negative_style(NegativeStyle::RedParentheses)
+        ),
+        "#,##0_);[Red](#,##0)"
+    );
+    assert_eq!(
+        code(spec(NumberFormatKind::Percent).with_decimals(1)),
+        "0.0%"
+    );
+    assert_eq!(code(spec(NumberFormatKind::Scientific)), "0.00E+00");
+    assert_eq!(
+        code(spec(NumberFormatKind::Fraction).with_decimals(2)),
+        "# ??/??"
+    );
+    assert_eq!(
+        code(
+            spec(NumberFormatKind::Number)
+                .with_color(Color::Named("blue".to_string()))
+                .with_negative_style(NegativeStyle::Parentheses)
+        ),
+        "[Blue]0.00_);[Blue](0.00)"
+    );
+}
+
+#[test]
+fn writes_currency_formats() {
+    let code = |s: NumberFormatSpec| s.to_pattern().unwrap();
+    assert_eq!(code(spec(NumberFormatKind::Currency)), "$#,##0.00");
+    assert_eq!(
+        code(
+            spec(NumberFormatKind::Currency)
+                .with_currency("€")
+                .with_locale("407")
+                .with_negative_style(NegativeStyle::RedMinus)
+        ),
+        "[$€-407]#,##0.00;[Red]-[$€-407]#,##0.00"
+    );
+    assert_eq!(
+        code(
+            spec(NumberFormatKind::Currency)
+                .with_currency("kr")
+                .with_currency_placement(CurrencyPlacement::AfterWithSpace)
+        ),
+        "#,##0.00 [$kr]"
+    );
+    assert_eq!(
+        code(
+            spec(NumberFormatKind::Currency)
+                .without_currency()
+                .with_locale("de-DE")
+        ),
+        "[$-de-DE]#,##0.00"
+    );
+}
+
+#[test]
+fn writes_accounting_formats() {
+    assert_eq!(
+        spec(NumberFormatKind::Accounting).to_pattern().unwrap(),
+        "_($* #,##0.00_);_($* (#,##0.00);_($* \"-\"??_);_(@_)"
+    );
+    assert_eq!(
+        spec(NumberFormatKind::Accounting)
+            .with_currency("€")
+            .with_locale("407")
+            .with_currency_placement(CurrencyPlacement::AfterWithSpace)
+            .to_pattern()
+            .unwrap(),
+        "_-* #,##0.00 [$€-407]_-;-* #,##0.00 [$€-407]_-;_-* \"-\"?? [$€-407]_-;_-@_-"
+    );
+}
+
+#[test]
+fn writes_dates_and_times() {
+    assert_eq!(
+        spec(NumberFormatKind::Date).to_pattern().unwrap(),
+        "yyyy-mm-dd"
+    );
+    assert_eq!(
+        spec(NumberFormatKind::Time)
+            .with_decimals(2)
+            .with_locale("409")
+            .to_pattern()
+            .unwrap(),
+        "[$-409]h:mm:ss.00"
+    );
+}
+
+#[test]
+fn generated_codes_render_as_configured() {
+    let euro = spec(NumberFormatKind::Currency)
+        .with_currency("€")
+        .with_locale("407")
+        .with_negative_style(NegativeStyle::RedMinus)
+        .to_pattern()
+        .unwrap();
+    assert_eq!(render(&euro, -1234.5), "-€1.234,50");
+
+    let parens = spec(NumberFormatKind::Currency)
+        .with_negative_style(NegativeStyle::Parentheses)
+        .to_pattern()
+        .unwrap();
+    assert_eq!(render(&parens, -1234.5), "($1,234.50)");
+    assert_eq!(render(&parens, 1234.5), "$1,234.50 ");
+
+    let time = spec(NumberFormatKind::Time).to_pattern().unwrap();
+    assert_eq!(render(&time, 0.5), "12:00:00");
+}
+
+#[test]
+fn every_combination_parses() {
+    let kinds = [
+        NumberFormatKind::Number,
+        NumberFormatKind::Currency,
+        NumberFormatKind::Accounting,
+        NumberFormatKind::Percent,
+        NumberFormatKind::Scientific,
+        NumberFormatKind::Fraction,
+        NumberFormatKind::Date,
+        NumberFormatKind::Time,
+    ];
+    let styles = [
+        NegativeStyle::Minus,
+        NegativeStyle::Parentheses,
+        NegativeStyle::Red,
+        NegativeStyle::RedMinus,
+        NegativeStyle::RedParentheses,
+    ];
+    let placements = [
+        CurrencyPlacement::Before,
+        CurrencyPlacement::BeforeWithSpace,
+        CurrencyPlacement::After,
+        CurrencyPlacement::AfterWithSpace,
+    ];
+    for kind in kinds {
+        for style in styles {
+            for placement in placements {
+                for symbol in [None, Some("$"), Some("€"), Some("CHF-x")] {
+                    for locale in [None, Some("407")] {
+                        let mut s = spec(kind)
+                            .with_negative_style(style)
+                            .with_currency_placement(placement)
+                            .with_decimals(3);
+                        s.currency = symbol.map(str::to_string);
+                        s.locale = locale.map(str::to_string);
+                        let code = s.to_pattern();
+                        assert!(code.is_ok(), "{s:?} gave {code:?}");
+                        render(&code.unwrap(), -12.5);
+                    }
+                }
+            }
+        }
+    }
+}
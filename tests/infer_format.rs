@@ -0,0 +1,101 @@
+use numfmt_rs::{
+    FormatValue, FormatterOptions, format_with_options, infer_value_and_format, parse_pattern,
+};
+
+fn infer(text: &str, locale: &str) -> (FormatValue<'static>, String) {
+    let options = FormatterOptions::default()
+        .with_locale(locale)
+        .with_reference_year(2024);
+    infer_value_and_format(text, &options)
+}
+
+#[test]
+fn infers_number_formats() {
+    assert_eq!(infer("15%", ""), (FormatValue::Number(0.15), "0%".into()));
+    assert_eq!(
+        infer("12.5%", ""),
+        (FormatValue::Number(0.125), "0.00%".into())
+    );
+    assert_eq!(
+        infer("$1,000", ""),
+        (FormatValue::Number(1000.0), "$#,##0".into())
+    );
+    assert_eq!(
+        infer("3,50 €", "de"),
+        (FormatValue::Number(3.5), "#,##0.00 [$€]".into())
+    );
+    assert_eq!(
+        infer("(1,200)", ""),
+        (FormatValue::Number(-1200.0), "#,##0_);(#,##0)".into())
+    );
+    assert_eq!(
+        infer("1.5E+3", ""),
+        (FormatValue::Number(1500.0), "0.00E+00".into())
+    );
+    assert_eq!(
+        infer("1 3/4", ""),
+        (FormatValue::Number(1.75), "# ?/?".into())
+    );
+    assert_eq!(
+        infer("42", ""),
+        (FormatValue::Number(42.0), "General".into())
+    );
+}
+
+#[test]
+fn infers_dates_times_and_fallbacks() {
+    let (value, pattern) = infer("3-Mar-2024", "");
+    assert_eq!(value, FormatValue::Number(45354.0));
+    assert_eq!(pattern, "d-mmm-yyyy");
+    assert_eq!(
+        infer("3-Mar", ""),
+        (FormatValue::Number(45354.0), "d-mmm".into())
+    );
+    // Without a reference year, year-less dates stay text.
+    assert_eq!(
+        infer_value_and_format("3-Mar", &FormatterOptions::default()),
+        (FormatValue::Text("3-Mar".into()), "General".into())
+    );
+    assert_eq!(infer("1/2", "en-US").1, "m/d");
+    assert_eq!(infer("13:05", "").1, "h:mm");
+    assert_eq!(
+        infer("true", ""),
+        (FormatValue::Boolean(true), "General".into())
+    );
+    assert_eq!(
+        infer("hello", ""),
+        (FormatValue::Text("hello".into()), "General".into())
+    );
+    for text in ["$5%", "12-", "NaN", "∞", "-∞"] {
+        assert_eq!(
+            infer(text, ""),
+            (FormatValue::Text(text.into()), "General".into())
+        );
+    }
+    assert_eq!(infer("  ", ""), (FormatValue::Null, "General".into()));
+}
+
+#[test]
+fn inferred_formats_render_the_typed_text() {
+    for (text, locale) in [
+        ("15%", ""),
+        ("12.50%", ""),
+        ("$1,000", ""),
+        ("$1,000.50", ""),
+        ("-$5", ""),
+        ("(1,200)", ""),
+        ("3,50 €", "de"),
+        ("1.234,56", "de"),
+        ("1.50E+03", ""),
+        ("1 3/4", ""),
+        ("3-Mar-2024", ""),
+        ("1/2", "en-US"),
+        ("1:05 PM", ""),
+    ] {
+        let (value, pattern) = infer(text, locale);
+        assert!(parse_pattern(&pattern).is_ok(), "{pattern}");
+        let options = FormatterOptions::default().with_locale(locale);
+        let output = format_with_options(&pattern, value, options).unwrap();
+        assert_eq!(output, text, "{text} inferred {pattern}");
+    }
+}
@@ -0,0 +1,188 @@
+#![cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+
+use numfmt_rs::{FormatterOptions, FromSerial, TimeZone, format, format_with_options};
+
+const STAMP: &str = "yyyy-mm-dd hh:mm:ss.000";
+
+fn in_zone(zone: &str) -> FormatterOptions {
+    FormatterOptions::default().with_time_zone(TimeZone::parse(zone).unwrap())
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_values {
+    use super::*;
+    use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeDelta, Timelike, Utc};
+
+    fn naive() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 3, 5)
+            .unwrap()
+            .and_hms_nano_opt(14, 7, 9, 123_456_789)
+            .unwrap()
+    }
+
+    #[test]
+    fn formats_naive_and_zoned_values() {
+        assert_eq!(format(STAMP, naive()).unwrap(), "2024-03-05 14:07:09.123");
+        assert_eq!(format("yyyy-mm-dd", naive().date()).unwrap(), "2024-03-05");
+
+        let zoned: DateTime<FixedOffset> = "2024-03-05T14:07:09+02:00".parse().unwrap();
+        assert_eq!(
+            format_with_options(STAMP, zoned, in_zone("UTC")).unwrap(),
+            "2024-03-05 12:07:09.000"
+        );
+        assert_eq!(format(STAMP, zoned).unwrap(), "2024-03-05 14:07:09.000");
+    }
+
+    #[test]
+    fn formats_durations_as_elapsed_time() {
+        let delta = TimeDelta::hours(27) + TimeDelta::milliseconds(250);
+        assert_eq!(format("[h]:mm:ss.00", delta).unwrap(), "27:00:00.25");
+    }
+
+    #[test]
+    fn reads_serials_back() {
+        let options = FormatterOptions::default();
+        let serial = 45_356.5 + 1.5 / 86_400.0;
+        let value = NaiveDateTime::from_serial(serial, &options).unwrap();
+        assert_eq!(value.to_string(), "2024-03-05 12:00:01.500");
+        assert_eq!(
+            NaiveDate::from_serial(serial, &options)
+                .unwrap()
+                .to_string(),
+            "2024-03-05"
+        );
+        // The phantom 1900-02-29 has no real date.
+        assert_eq!(NaiveDate::from_serial(60.0, &options), None);
+        assert_eq!(
+            NaiveDate::from_serial(59.0, &options).unwrap().to_string(),
+            "1900-02-28"
+        );
+        assert_eq!(
+            NaiveDate::from_serial(61.0, &options).unwrap().to_string(),
+            "1900-03-01"
+        );
+
+        let modern = FormatterOptions {
+            leap_1900: false,
+            ..FormatterOptions::default()
+        };
+        for (serial, shown) in [(0.0, "1899-12-30"), (30.0, "1900-01-29")] {
+            assert_eq!(
+                format_with_options("yyyy-mm-dd", serial, modern.clone()).unwrap(),
+                shown
+            );
+            assert_eq!(
+                NaiveDate::from_serial(serial, &modern).unwrap().to_string(),
+                shown
+            );
+        }
+        // Negative 1904 serials are negative times, not earlier dates.
+        let mac = FormatterOptions::default().with_date_1904(true);
+        assert_eq!(NaiveDate::from_serial(-1.0, &mac), None);
+        assert_eq!(DateTime::<Utc>::from_serial(-1.0, &mac), None);
+
+        let utc = DateTime::<Utc>::from_serial(45_356.5, &in_zone("+02:00")).unwrap();
+        assert_eq!(utc.to_rfc3339(), "2024-03-05T10:00:00+00:00");
+        assert_eq!(
+            TimeDelta::from_serial(-1.25, &options),
+            Some(TimeDelta::hours(-30))
+        );
+    }
+
+    #[test]
+    fn round_trips_through_the_1904_system() {
+        let options = FormatterOptions::default().with_date_1904(true);
+        let value = naive().with_nanosecond(123_000_000).unwrap();
+        let serial: f64 = format_with_options("0.000000000", value, options.clone())
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(NaiveDateTime::from_serial(serial, &options), Some(value));
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_values {
+    use super::*;
+    use time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+    fn datetime(month: Month, day: u8, time: Time) -> PrimitiveDateTime {
+        PrimitiveDateTime::new(Date::from_calendar_date(2024, month, day).unwrap(), time)
+    }
+
+    #[test]
+    fn formats_primitive_and_offset_values() {
+        let value = datetime(
+            Month::March,
+            5,
+            Time::from_hms_nano(14, 7, 9, 987_654_321).unwrap(),
+        );
+        assert_eq!(format(STAMP, value).unwrap(), "2024-03-05 14:07:09.988");
+        assert_eq!(format("yyyy-mm-dd", value.date()).unwrap(), "2024-03-05");
+
+        let offset = value.assume_offset(UtcOffset::from_hms(-5, 0, 0).unwrap());
+        assert_eq!(
+            format_with_options(STAMP, offset, in_zone("UTC")).unwrap(),
+            "2024-03-05 19:07:09.988"
+        );
+    }
+
+    #[test]
+    fn converts_durations_and_serials() {
+        assert_eq!(format("[mm]:ss", Duration::seconds(90)).unwrap(), "01:30");
+        assert_eq!(format("[mm]:ss", Duration::seconds(-90)).unwrap(), "######");
+        let options = in_zone("+02:00");
+        let value = OffsetDateTime::from_serial(45_500.5, &options).unwrap();
+        assert_eq!(value.offset().whole_seconds(), 7_200);
+        assert_eq!(
+            PrimitiveDateTime::from_serial(45_500.5, &options),
+            Some(datetime(Month::July, 27, Time::from_hms(12, 0, 0).unwrap()))
+        );
+        assert_eq!(
+            Duration::from_serial(0.5, &options),
+            Some(Duration::hours(12))
+        );
+        let mac = FormatterOptions::default().with_date_1904(true);
+        assert_eq!(PrimitiveDateTime::from_serial(-1.0, &mac), None);
+    }
+}
+
+#[cfg(feature = "jiff")]
+mod jiff_values {
+    use super::*;
+    use jiff::{SignedDuration, Timestamp, Zoned, civil, tz};
+
+    #[test]
+    fn formats_civil_and_zoned_values() {
+        let value = civil::date(2024, 3, 5).at(14, 7, 9, 5_000_000);
+        assert_eq!(format(STAMP, value).unwrap(), "2024-03-05 14:07:09.005");
+
+        let zoned = value.to_zoned(tz::TimeZone::fixed(tz::offset(9))).unwrap();
+        assert_eq!(
+            format_with_options(STAMP, zoned, in_zone("UTC")).unwrap(),
+            "2024-03-05 05:07:09.005"
+        );
+        let stamp: Timestamp = "2024-03-05T05:07:09Z".parse().unwrap();
+        assert_eq!(format("hh:mm", stamp).unwrap(), "05:07");
+    }
+
+    #[test]
+    fn converts_durations_and_serials() {
+        let elapsed = SignedDuration::from_secs(3_600 * 50);
+        assert_eq!(format("[h]", elapsed).unwrap(), "50");
+
+        let options = in_zone("-03:00");
+        let zoned = Zoned::from_serial(45_356.25, &options).unwrap();
+        assert_eq!(zoned.to_string(), "2024-03-05T06:00:00-03:00[-03:00]");
+        assert_eq!(
+            civil::DateTime::from_serial(45_356.25, &options),
+            Some(civil::date(2024, 3, 5).at(6, 0, 0, 0))
+        );
+        assert_eq!(
+            SignedDuration::from_serial(1.0, &options),
+            Some(SignedDuration::from_hours(24))
+        );
+        let mac = FormatterOptions::default().with_date_1904(true);
+        assert_eq!(civil::Date::from_serial(-1.0, &mac), None);
+    }
+}
@@ -0,0 +1,120 @@
+use std::sync::Arc;
+
+use numfmt_rs::{
+    CompiledFormat, FormatterOptions, LocaleRegistry, LocaleSettings, add_locale, format,
+    format_with_options, get_locale_settings, list_locales, remove_locale,
+};
+
+fn settings(decimal: &str, group: &str) -> LocaleSettings {
+    LocaleSettings {
+        decimal: Some(decimal.to_string()),
+        group: Some(group.to_string()),
+        ..LocaleSettings::default()
+    }
+}
+
+#[test]
+fn owned_registries_are_independent() {
+    let mut registry = LocaleRegistry::new();
+    registry.add_locale(settings("·", "'"), "tenant-A").unwrap();
+    assert!(registry.list_locales().contains(&"tenant_A".to_string()));
+    assert!(registry.list_locales().contains(&"tenant".to_string()));
+    assert!(!list_locales().contains(&"tenant_A".to_string()));
+
+    let options = FormatterOptions::default()
+        .with_locale("tenant-A")
+        .with_locale_registry(Arc::new(registry.clone()));
+    assert_eq!(
+        format_with_options("#,##0.00", 1234.5, options).unwrap(),
+        "1'234·50"
+    );
+    // The shared registry does not know the tenant and falls back.
+    assert_eq!(
+        format_with_options(
+            "#,##0.00",
+            1234.5,
+            FormatterOptions::default().with_locale("tenant-A")
+        )
+        .unwrap(),
+        "1,234.50"
+    );
+
+    assert!(registry.remove_locale("tenant-A"));
+    assert!(!registry.remove_locale("tenant-A"));
+    assert!(!registry.list_locales().contains(&"tenant".to_string()));
+    assert!(registry.get_locale("tenant-A").is_none());
+}
+
+#[test]
+fn replacing_a_locale_updates_its_language_entry() {
+    let mut registry = LocaleRegistry::new();
+    registry.add_locale(settings(",", "."), "zz-ZZ").unwrap();
+    registry.add_locale(settings("/", " "), "zz-ZZ").unwrap();
+    assert_eq!(registry.get_locale("zz").unwrap().decimal, "/");
+
+    // Bundled language entries are left alone.
+    registry.add_locale(settings("/", " "), "de-XX").unwrap();
+    assert_eq!(registry.get_locale("de").unwrap().decimal, ",");
+    assert!(registry.remove_locale("de-XX"));
+    assert!(registry.get_locale("de").is_some());
+}
+
+#[test]
+fn reports_locale_settings() {
+    let registry = LocaleRegistry::new();
+    let de = registry.get_locale_settings("de-DE").unwrap();
+    assert_eq!(de.decimal.as_deref(), Some(","));
+    assert_eq!(de.group.as_deref(), Some("."));
+    assert!(registry.get_locale_settings("").is_none());
+
+    let shared = get_locale_settings("407").unwrap();
+    assert_eq!(shared.decimal.as_deref(), Some(","));
+}
+
+#[test]
+fn manages_the_shared_registry() {
+    add_locale(settings(";", "_"), "qq-QQ").unwrap();
+    assert!(list_locales().contains(&"qq_QQ".to_string()));
+    assert_eq!(format("[$-qq-QQ]#,##0.0", 1234.5).unwrap(), "1_234;5");
+    assert!(remove_locale("qq-QQ"));
+    assert!(!list_locales().contains(&"qq_QQ".to_string()));
+    assert!(get_locale_settings("qq-QQ").is_none());
+}
+
+#[test]
+fn inline_locales_override_tags() {
+    let registry = LocaleRegistry::new();
+    let base = registry.get_locale("en").unwrap();
+    let inline = Arc::new(base.with_settings(settings(",", " ")));
+    let options = FormatterOptions::default()
+        .with_locale("de")
+        .with_inline_locale(inline);
+    assert_eq!(
+        format_with_options("[$-409]#,##0.00", 1234.5, options.clone()).unwrap(),
+        "1 234,50"
+    );
+    let compiled = CompiledFormat::new("#,##0.0", options).unwrap();
+    assert_eq!(compiled.format(-1234.5).unwrap(), "-1 234,5");
+}
+
+#[test]
+fn exponents_use_the_locale_signs() {
+    let mut registry = LocaleRegistry::new();
+    let settings = LocaleSettings {
+        negative: Some("\u{2212}".to_string()),
+        positive: Some("\u{FF0B}".to_string()),
+        ..LocaleSettings::default()
+    };
+    registry.add_locale(settings, "xx").unwrap();
+    let options = FormatterOptions::default()
+        .with_locale("xx")
+        .with_locale_registry(Arc::new(registry));
+    assert_eq!(
+        format_with_options("0.00E+00", 0.000123, options.clone()).unwrap(),
+        "1.23E\u{2212}04"
+    );
+    assert_eq!(
+        format_with_options("0.00E+00", 12300.0, options).unwrap(),
+        "1.23E\u{FF0B}04"
+    );
+}
@@ -0,0 +1,212 @@
+use numfmt_rs::parser::NumeralTag;
+use numfmt_rs::{
+    BuiltinNumerals, CompiledFormat, DateValue, FormatterOptions, NumeralSystem,
+    add_numeral_system, format, format_with_options, parse_pattern, remove_numeral_system,
+};
+
+fn spell(system: BuiltinNumerals, value: f64) -> String {
+    system.format(value)
+}
+
+#[test]
+fn reads_numeral_tags() {
+    let numerals = |pattern: &str| parse_pattern(pattern).unwrap().partitions[0].numerals;
+    assert_eq!(numerals("[DBNum1]General"), Some(NumeralTag::DbNum(1)));
+    assert_eq!(numerals("[natnum12]0"), Some(NumeralTag::NatNum(12)));
+    assert_eq!(numerals("[NatNum]0"), None);
+    assert_eq!(
+        parse_pattern("[NatNum3]0.00").unwrap().to_string(),
+        "[NatNum3]0.00"
+    );
+}
+
+#[test]
+fn spells_numbers_out() {
+    use BuiltinNumerals::*;
+    assert_eq!(spell(ChineseSimplified, 10_010.0), "一万〇一十");
+    assert_eq!(spell(ChineseTraditional, -120_000.0), "負一十二萬");
+    assert_eq!(spell(ChineseTraditionalFormal, 1234.0), "壹仟貳佰參拾肆");
+    assert_eq!(spell(Japanese, 1234.0), "千二百三十四");
+    assert_eq!(spell(Japanese, 10_101.0), "一万百一");
+    assert_eq!(spell(JapaneseFormal, 1234.0), "壱阡弐百参拾四");
+    assert_eq!(spell(KoreanHangul, 1234.0), "천이백삼십사");
+    assert_eq!(spell(KoreanHangul, 20_015.0), "이만십오");
+    assert_eq!(spell(KoreanHanja, 20_015.0), "二萬十五");
+}
+
+#[test]
+fn writes_digits_in_place() {
+    use BuiltinNumerals::*;
+    assert_eq!(spell(ArabicIndic, -12.5), "-١٢٫٥");
+    assert_eq!(spell(Devanagari, 2024.0), "२०२४");
+    assert_eq!(spell(Thai, 0.25), "๐.๒๕");
+    assert_eq!(spell(Roman, 1994.0), "MCMXCIV");
+    assert_eq!(spell(Roman, 4000.0), "4000");
+    assert_eq!(spell(Roman, 2.5), "2.5");
+}
+
+#[test]
+fn registered_systems_handle_their_tags() {
+    add_numeral_system(NumeralTag::NatNum(40), BuiltinNumerals::Thai);
+    add_numeral_system(NumeralTag::DbNum(40), BuiltinNumerals::Japanese);
+
+    assert_eq!(format("[NatNum40]#,##0.00", 1234.5).unwrap(), "๑,๒๓๔.๕๐");
+    assert_eq!(format("[NatNum40]General", -7).unwrap(), "-๗");
+    assert_eq!(format("[DBNum40]General", 110).unwrap(), "百十");
+    let date = DateValue::new(2024).with_month(3).with_day(5);
+    assert_eq!(
+        format("[NatNum40]yyyy-mm-dd", date.clone()).unwrap(),
+        "๒๐๒๔-๐๓-๐๕"
+    );
+    assert_eq!(
+        format("[DBNum40]yyyy\"年\"m\"月\"d\"日\"", date).unwrap(),
+        "二〇二四年三月五日"
+    );
+
+    assert!(remove_numeral_system(NumeralTag::NatNum(40)));
+    assert!(!remove_numeral_system(NumeralTag::NatNum(40)));
+    assert_eq!(format("[NatNum40]#,##0.00", 1234.5).unwrap(), "1,234.50");
+}
+
+struct Binary;
+
+impl NumeralSystem for Binary {
+    fn digits(&self) -> [&str; 10] {
+        ["○", "●", "2", "3", "4", "5", "6", "7", "8", "9"]
+    }
+}
+
+#[test]
+fn user_systems_can_replace_built_in_tags() {
+    assert_eq!(format("[DBNum4]General", 10).unwrap(), "１０");
+    add_numeral_system(NumeralTag::DbNum(4), Binary);
+    assert_eq!(format("[DBNum4]General", 10).unwrap(), "●○");
+    assert!(remove_numeral_system(NumeralTag::DbNum(4)));
+    assert_eq!(format("[DBNum4]General", 10).unwrap(), "１０");
+}
+
+#[test]
+fn roman_numerals_write_whole_values() {
+    add_numeral_system(NumeralTag::DbNum(42), BuiltinNumerals::Roman);
+    assert_eq!(format("[DBNum42]0", 12).unwrap(), "XII");
+    assert_eq!(format("[DBNum42]General", 1994).unwrap(), "MCMXCIV");
+    let date = DateValue::new(2024).with_month(3).with_day(5);
+    assert_eq!(format("[DBNum42]d/m/yyyy", date).unwrap(), "V/III/MMXXIV");
+    assert!(remove_numeral_system(NumeralTag::DbNum(42)));
+}
+
+#[test]
+fn compiled_formats_keep_the_system_they_resolved() {
+    add_numeral_system(NumeralTag::NatNum(41), BuiltinNumerals::Thai);
+    let compiled = CompiledFormat::new("[NatNum41]0", FormatterOptions::default()).unwrap();
+    assert!(remove_numeral_system(NumeralTag::NatNum(41)));
+    assert_eq!(compiled.format(42).unwrap(), "๔๒");
+    assert_eq!(format("[NatNum41]0", 42).unwrap(), "42");
+}
+
+#[test]
+fn natnum_follows_the_section_locale() {
+    let cases = [
+        ("[NatNum1][$-804]General", "〇.一二"),
+        ("[NatNum2][$-804]General", "零.壹贰"),
+        ("[NatNum4][$-zh-TW]General", "〇.一二"),
+        ("[NatNum1][$-411]#,##0", "〇"),
+        ("[NatNum1][$-th-TH]#,##0.00", "๐.๑๒"),
+    ];
+    for (pattern, expected) in cases {
+        assert_eq!(format(pattern, 0.12).unwrap(), expected, "{pattern}");
+    }
+    // Moroccan Arabic keeps European digits.
+    assert_eq!(
+        format("[NatNum1][$-1801]General", 0.12).unwrap(),
+        format("[$-1801]General", 0.12).unwrap()
+    );
+
+    let value = 12_345;
+    let cases = [
+        ("[NatNum1][$-411]General", "一二三四五"),
+        ("[NatNum3][$-411]#,##0", "１２,３４５"),
+        ("[NatNum4][$-411]General", "一万二千三百四十五"),
+        ("[NatNum5][$-411]General", "壱萬弐阡参百四拾伍"),
+        ("[NatNum6][$-411]General", "１万２千３百４十５"),
+        ("[NatNum7][$-411]General", "一万二千三百四十五"),
+        ("[NatNum8][$-411]General", "壱萬弐阡参百四拾伍"),
+        ("[NatNum4][$-804]General", "一万二千三百四十五"),
+        ("[NatNum5][$-404]General", "壹萬貳仟參佰肆拾伍"),
+        ("[NatNum9][$-412]General", "일이삼사오"),
+        ("[NatNum10][$-412]General", "일만이천삼백사십오"),
+        ("[NatNum11][$-412]General", "일만이천삼백사십오"),
+        ("[NatNum1][$-401]#,##0", "١٢٬٣٤٥"),
+        ("[NatNum1][$-439]0", "१२३४५"),
+        (
+            "[NatNum12]General",
+            "twelve thousand three hundred forty-five",
+        ),
+        ("[NatNum12][$-407]0", "12345"),
+    ];
+    for (pattern, expected) in cases {
+        assert_eq!(format(pattern, value).unwrap(), expected, "{pattern}");
+    }
+}
+
+#[test]
+fn natnum_applies_to_dates_and_options() {
+    let date = DateValue::new(2024).with_month(11).with_day(5);
+    assert_eq!(
+        format("[NatNum1][$-411]yyyy\"年\"m\"月\"d\"日\"", date.clone()).unwrap(),
+        "二〇二四年一一月五日"
+    );
+    assert_eq!(
+        format("[NatNum7][$-411]yyyy\"年\"m\"月\"d\"日\"", date.clone()).unwrap(),
+        "二〇二四年十一月五日"
+    );
+    assert_eq!(
+        format("[NatNum12]d mmm yyyy", date).unwrap(),
+        "five Nov two thousand twenty-four"
+    );
+    let options = FormatterOptions::default().with_locale("ko-KR");
+    assert_eq!(
+        format_with_options("[NatNum10]0", 110, options.clone()).unwrap(),
+        "일백일십"
+    );
+    assert_eq!(
+        format_with_options("[NatNum11]0", 110, options).unwrap(),
+        "백십"
+    );
+    assert_eq!(
+        format("[NatNum12]General", -1.5).unwrap(),
+        "minus one point five"
+    );
+}
+
+#[test]
+fn natnum_leaves_text_alone() {
+    assert_eq!(format("[NatNum1][$-41E]@", "Room 12").unwrap(), "Room 12");
+}
+
+#[test]
+fn spelled_systems_keep_the_rest_of_the_section() {
+    assert_eq!(format("[NatNum12][$-en-US]0%", 0.5).unwrap(), "fifty%");
+    assert_eq!(
+        format("[NatNum12]\"due in \"0\" days\"", 3).unwrap(),
+        "due in three days"
+    );
+    assert_eq!(
+        format("[NatNum12]0.00", -1.5).unwrap(),
+        "minus one point five zero"
+    );
+    assert_eq!(format("[DBNum1]#,##0", 1234).unwrap(), "一千二百三十四");
+    assert_eq!(format("[DBNum1]0.00", 10.5).unwrap(), "一十.五〇");
+    assert_eq!(
+        format("[NatNum12]0", 1e20).unwrap(),
+        "one hundred quintillion"
+    );
+    assert_eq!(format("[NatNum12]0", 2e36).unwrap(), "two undecillion");
+    // Past u128 there are no more scale words and the digits stay.
+    assert_eq!(
+        format("[NatNum12]0", 1e39).unwrap(),
+        format("0", 1e39).unwrap()
+    );
+    // Digits in literals are not numbers of the value.
+    assert_eq!(format("[DBNum1]\"Q1 \"0", 3).unwrap(), "Q1 三");
+}
@@ -0,0 +1,100 @@
+use numfmt_rs::{DateValue, FormatterOptions, format_with_options, parse_date};
+
+fn parse(text: &str, locale: &str) -> Option<(DateValue, f64, String)> {
+    parse_date(text, &FormatterOptions::default().with_locale(locale))
+}
+
+#[test]
+fn uses_prefer_mdy_for_numeric_dates() {
+    let (date, serial, pattern) = parse("3/4/2024", "en-US").unwrap();
+    assert_eq!((date.month, date.day), (Some(3), Some(4)));
+    assert_eq!(serial, 45355.0);
+    assert_eq!(pattern, "m/d/yyyy");
+
+    let (date, serial, pattern) = parse("3/4/2024", "en-GB").unwrap();
+    assert_eq!((date.month, date.day), (Some(4), Some(3)));
+    assert_eq!(serial, 45385.0);
+    assert_eq!(pattern, "d/m/yyyy");
+
+    // Only one reading is a valid date.
+    let (date, _, pattern) = parse("13/4/2024", "en-US").unwrap();
+    assert_eq!((date.month, date.day), (Some(4), Some(13)));
+    assert_eq!(pattern, "d/m/yyyy");
+}
+
+#[test]
+fn parses_month_names_and_iso_dates() {
+    let (_, serial, pattern) = parse("4 March 2024", "").unwrap();
+    assert_eq!(serial, 45355.0);
+    assert_eq!(pattern, "d mmmm yyyy");
+
+    let (_, serial, pattern) = parse("March 4, 2024", "").unwrap();
+    assert_eq!(serial, 45355.0);
+    assert_eq!(pattern, "mmmm d, yyyy");
+
+    let (_, serial, pattern) = parse("04-Mar-24", "").unwrap();
+    assert_eq!(serial, 45355.0);
+    assert_eq!(pattern, "dd-mmm-yy");
+
+    let (_, serial, pattern) = parse("4 mars 2024", "fr").unwrap();
+    assert_eq!(serial, 45355.0);
+    assert_eq!(pattern, "d mmmm yyyy");
+
+    let (_, serial, pattern) = parse("2024-03-04 13:05", "").unwrap();
+    assert!((serial - (45355.0 + 13.0 / 24.0 + 5.0 / 1440.0)).abs() < 1e-9);
+    assert_eq!(pattern, "yyyy-mm-dd h:mm");
+}
+
+#[test]
+fn honors_the_1900_leap_bug() {
+    assert_eq!(parse("2/29/1900", "en-US").unwrap().1, 60.0);
+    assert_eq!(parse("3/1/1900", "en-US").unwrap().1, 61.0);
+    let options = FormatterOptions {
+        leap_1900: false,
+        ..FormatterOptions::default()
+    };
+    assert!(parse_date("1900-02-29", &options).is_none());
+}
+
+#[test]
+fn rejects_non_dates() {
+    assert!(parse("2/30/2024", "en-US").is_none());
+    assert!(parse("hello", "").is_none());
+    assert!(parse("4 Foo 2024", "").is_none());
+    assert!(parse("1/1/1899", "").is_none());
+}
+
+#[test]
+fn detected_pattern_renders_the_input() {
+    let options = FormatterOptions::default().with_locale("en-US");
+    for text in ["3/4/2024", "4 March 2024", "2024-03-04 13:05"] {
+        let (_, serial, pattern) = parse_date(text, &options).unwrap();
+        let output = format_with_options(&pattern, serial, options.clone()).unwrap();
+        assert_eq!(output, text);
+    }
+}
+
+#[test]
+fn parses_datetimes_with_meridiem() {
+    let (date, serial, pattern) = parse("3/4/2024 1:05:30 PM", "en-US").unwrap();
+    assert_eq!(
+        (date.hour, date.minute, date.second),
+        (Some(13), Some(5), Some(30))
+    );
+    assert!((serial - (45355.0 + 47_130.0 / 86_400.0)).abs() < 1e-9);
+    assert_eq!(pattern, "m/d/yyyy h:mm:ss AM/PM");
+    assert!(parse("3/4/2024 37:00", "en-US").is_none());
+}
+
+#[test]
+fn takes_missing_years_from_the_options() {
+    let options = FormatterOptions::default()
+        .with_locale("en-US")
+        .with_reference_year(2024);
+    let (date, serial, pattern) = parse_date("3/4", &options).unwrap();
+    assert_eq!((date.year, date.month, date.day), (2024, Some(3), Some(4)));
+    assert_eq!(serial, 45355.0);
+    assert_eq!(pattern, "m/d");
+    assert!(parse("3/4", "en-US").is_none());
+    assert!(parse("3-Mar", "").is_none());
+}
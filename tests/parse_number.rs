@@ -0,0 +1,61 @@
+use num_bigint::BigInt;
+use numfmt_rs::{FormatValue, FormatterOptions, format_with_options, parse_number};
+use std::str::FromStr;
+
+fn parse(text: &str, locale: &str) -> Option<FormatValue<'static>> {
+    parse_number(text, &FormatterOptions::default().with_locale(locale))
+}
+
+#[test]
+fn parses_plain_and_grouped_numbers() {
+    assert_eq!(parse("1234", ""), Some(FormatValue::Number(1234.0)));
+    assert_eq!(parse(" 1,234.5 ", ""), Some(FormatValue::Number(1234.5)));
+    assert_eq!(parse(".5", ""), Some(FormatValue::Number(0.5)));
+    assert_eq!(parse("+7", ""), Some(FormatValue::Number(7.0)));
+    assert_eq!(parse("12-", ""), Some(FormatValue::Number(-12.0)));
+    assert_eq!(parse("1,23", ""), None);
+    assert_eq!(parse("1,234,56", ""), None);
+    assert_eq!(parse("abc", ""), None);
+    assert_eq!(parse("", ""), None);
+}
+
+#[test]
+fn parses_locale_separators() {
+    assert_eq!(parse("1.234,56", "de"), Some(FormatValue::Number(1234.56)));
+    assert_eq!(parse("1 234,5", "fr"), Some(FormatValue::Number(1234.5)));
+    assert_eq!(parse("1.234,56", "en"), None);
+}
+
+#[test]
+fn parses_negatives_percent_currency_and_exponents() {
+    assert_eq!(parse("(1,200)", ""), Some(FormatValue::Number(-1200.0)));
+    assert_eq!(parse("-$5", ""), Some(FormatValue::Number(-5.0)));
+    assert_eq!(parse("12.5%", ""), Some(FormatValue::Number(0.125)));
+    assert_eq!(parse("1.1%", ""), Some(FormatValue::Number(0.011)));
+    assert_eq!(parse("€ 3,50", "de"), Some(FormatValue::Number(3.5)));
+    assert_eq!(parse("3,50 €", "de"), Some(FormatValue::Number(3.5)));
+    assert_eq!(parse("1.2E+3", ""), Some(FormatValue::Number(1200.0)));
+    assert_eq!(parse("5e-1", ""), Some(FormatValue::Number(0.5)));
+    assert_eq!(parse("(-5)", ""), None);
+}
+
+#[test]
+fn parses_large_integers_as_bigint() {
+    let expected = BigInt::from_str("123456789012345678901").unwrap();
+    assert_eq!(
+        parse("123,456,789,012,345,678,901", ""),
+        Some(FormatValue::BigInt(expected))
+    );
+}
+
+#[test]
+fn reads_back_formatted_output() {
+    let options = FormatterOptions::default().with_locale("de");
+    for value in [0.0, 1234.5, -98765.25, 0.75] {
+        let text = format_with_options("#,##0.00", value, options.clone()).unwrap();
+        assert_eq!(
+            parse_number(&text, &options),
+            Some(FormatValue::Number(value))
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use numfmt_rs::{FormatterOptions, format_with_options, parse_time};
+
+fn parse(text: &str, locale: &str) -> Option<(f64, String)> {
+    parse_time(text, &FormatterOptions::default().with_locale(locale))
+}
+
+fn seconds(serial: f64) -> f64 {
+    (serial * 86_400.0 * 1000.0).round() / 1000.0
+}
+
+#[test]
+fn parses_clock_times() {
+    let (serial, pattern) = parse("13:05", "").unwrap();
+    assert_eq!(seconds(serial), 47_100.0);
+    assert_eq!(pattern, "h:mm");
+
+    let (serial, pattern) = parse("1:05 PM", "").unwrap();
+    assert_eq!(seconds(serial), 47_100.0);
+    assert_eq!(pattern, "h:mm AM/PM");
+
+    let (serial, _) = parse("12:30 am", "").unwrap();
+    assert_eq!(seconds(serial), 1_800.0);
+
+    let (serial, pattern) = parse("1:05:07.250", "").unwrap();
+    assert_eq!(seconds(serial), 3_907.25);
+    assert_eq!(pattern, "h:mm:ss.000");
+
+    let (serial, pattern) = parse("5:07.2", "").unwrap();
+    assert_eq!(seconds(serial), 307.2);
+    assert_eq!(pattern, "mm:ss.0");
+}
+
+#[test]
+fn parses_localized_markers() {
+    let (serial, pattern) = parse("1:05 午後", "ja").unwrap();
+    assert_eq!(seconds(serial), 47_100.0);
+    assert_eq!(pattern, "h:mm AM/PM");
+}
+
+#[test]
+fn parses_leading_markers() {
+    let (serial, pattern) = parse("오후 3:30", "ko").unwrap();
+    assert_eq!(seconds(serial), 55_800.0);
+    assert_eq!(pattern, "AM/PM h:mm");
+
+    let (serial, pattern) = parse("下午3:30", "zh").unwrap();
+    assert_eq!(seconds(serial), 55_800.0);
+    assert_eq!(pattern, "AM/PMh:mm");
+
+    let (serial, _) = parse("上午12:05", "zh").unwrap();
+    assert_eq!(seconds(serial), 300.0);
+    assert!(parse("오후 13:30", "ko").is_none());
+    assert!(parse("P3:30", "").is_none());
+
+    for locale in ["ko", "zh"] {
+        let options = FormatterOptions::default().with_locale(locale);
+        for (pattern, value) in [("AM/PM h:mm", 0.6875), ("AM/PMh:mm:ss", 0.2)] {
+            let text = format_with_options(pattern, value, options.clone()).unwrap();
+            let (serial, detected) = parse(&text, locale).unwrap();
+            assert_eq!(detected, pattern, "{text}");
+            assert_eq!(seconds(serial), seconds(value));
+        }
+    }
+}
+
+#[test]
+fn parses_elapsed_durations() {
+    let (serial, pattern) = parse("37:15:00", "").unwrap();
+    assert_eq!(seconds(serial), 134_100.0);
+    assert_eq!(pattern, "[h]:mm:ss");
+
+    let (serial, pattern) = parse("-1:30", "").unwrap();
+    assert_eq!(seconds(serial), -5_400.0);
+    assert_eq!(pattern, "[h]:mm");
+}
+
+#[test]
+fn rejects_invalid_times() {
+    assert!(parse("13:05 PM", "").is_none());
+    assert!(parse("1:60", "").is_none());
+    assert!(parse("1:5", "").is_none());
+    assert!(parse("1:05 XM", "").is_none());
+    assert!(parse("noon", "").is_none());
+}
+
+#[test]
+fn round_trips_elapsed_rendering() {
+    for (pattern, value) in [("[h]:mm:ss", 1.552_083_333_333_333_3), ("h:mm:ss.000", 0.3)] {
+        let text = format_with_options(pattern, value, FormatterOptions::default()).unwrap();
+        let (serial, detected) = parse(&text, "").unwrap();
+        assert_eq!(detected, pattern);
+        assert_eq!(seconds(serial), seconds(value));
+    }
+}
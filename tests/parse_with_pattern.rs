@@ -0,0 +1,105 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use numfmt_rs::{
+    FormatterOptions, LocaleRegistry, LocaleSettings, format_with_options, parse_pattern,
+    parse_with_pattern,
+};
+
+fn read(text: &str, pattern: &str) -> Option<f64> {
+    let pattern = parse_pattern(pattern).unwrap();
+    parse_with_pattern(text, &pattern, &FormatterOptions::default())
+}
+
+#[test]
+fn reads_numbers_with_literals_and_sections() {
+    assert_eq!(read("$1,234.50 CR", "$#,##0.00 \"CR\""), Some(1234.5));
+    assert_eq!(read("(1,200)", "#,##0;(#,##0)"), Some(-1200.0));
+    assert_eq!(read("1,200", "#,##0;(#,##0)"), Some(1200.0));
+    assert_eq!(read("-42", "0"), Some(-42.0));
+    assert_eq!(read("zero", "0;-0;\"zero\""), Some(0.0));
+    assert_eq!(read("12.5%", "0.0%"), Some(0.125));
+    assert_eq!(read("1.23E+04", "0.00E+00"), Some(12300.0));
+    assert_eq!(read("1,235K", "#,##0,\"K\""), Some(1_235_000.0));
+    assert_eq!(read("3 1/4", "# ?/?"), Some(3.25));
+    assert_eq!(read("12", "General"), Some(12.0));
+    assert_eq!(read("12 CR", "$#,##0.00 \"CR\""), None);
+}
+
+#[test]
+fn uses_conditions_to_pick_the_sign() {
+    assert_eq!(read("5", "[<0]0;0"), Some(5.0));
+    assert_eq!(read("-5", "[<0]0;0"), Some(-5.0));
+    assert_eq!(read("big", "[>100]\"big\";0"), None);
+}
+
+#[test]
+fn reads_dates_and_times_as_serials() {
+    assert_eq!(read("03-Apr-24", "dd-mmm-yy"), Some(45385.0));
+    assert_eq!(read("2024-03-04", "yyyy-mm-dd"), Some(45355.0));
+    assert_eq!(read("1900-02-29", "yyyy-mm-dd"), Some(60.0));
+    assert_eq!(read("2024-02-30", "yyyy-mm-dd"), None);
+
+    let serial = read("3/4/2024 1:05 PM", "m/d/yyyy h:mm AM/PM").unwrap();
+    assert!((serial - (45355.0 + 13.0 / 24.0 + 5.0 / 1440.0)).abs() < 1e-9);
+
+    let serial = read("37:15:00", "[h]:mm:ss").unwrap();
+    assert!((serial - (37.25 / 24.0)).abs() < 1e-9);
+}
+
+#[test]
+fn round_trips_formatted_output() {
+    let options = FormatterOptions::default().with_locale("de");
+    let cases: &[(&str, &[f64])] = &[
+        ("#,##0.00", &[0.0, 1234.5, -98765.25]),
+        ("0.000%", &[0.011, -0.5]),
+        ("#,##0_);(#,##0)", &[1200.0, -1200.0]),
+        ("dd.mm.yyyy hh:mm:ss", &[45355.5, 367.25]),
+        ("00000", &[42.0]),
+        ("# ?/??", &[1.5, 2.75]),
+        ("# ??/??", &[1.5, -3.25]),
+        ("??/??", &[0.5]),
+        ("# ???/???", &[2.25]),
+    ];
+    for (code, values) in cases {
+        let pattern = parse_pattern(code).unwrap();
+        for value in *values {
+            let text = format_with_options(code, *value, options.clone()).unwrap();
+            let read = parse_with_pattern(&text, &pattern, &options);
+            assert_eq!(read, Some(*value), "{code} rendered {value} as {text:?}");
+        }
+    }
+}
+
+#[test]
+fn gives_up_on_patterns_with_too_many_readings() {
+    let code = "#\"\"".repeat(12);
+    let text = format!("{}x", "1".repeat(12));
+    let start = Instant::now();
+    assert_eq!(read(&text, &code), None);
+    assert_eq!(read("12121212121212x", &"dm".repeat(7)), None);
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[test]
+fn reads_exponent_signs_in_the_locale() {
+    let mut registry = LocaleRegistry::new();
+    let settings = LocaleSettings {
+        negative: Some("\u{2212}".to_string()),
+        positive: Some("\u{FF0B}".to_string()),
+        ..LocaleSettings::default()
+    };
+    registry.add_locale(settings, "xx").unwrap();
+    let options = FormatterOptions::default()
+        .with_locale("xx")
+        .with_locale_registry(Arc::new(registry));
+    let pattern = parse_pattern("0.00E+00").unwrap();
+    for value in [0.000123, 12300.0] {
+        let text = format_with_options("0.00E+00", value, options.clone()).unwrap();
+        assert_eq!(
+            parse_with_pattern(&text, &pattern, &options),
+            Some(value),
+            "{text}"
+        );
+    }
+}
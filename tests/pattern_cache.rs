@@ -0,0 +1,63 @@
+use numfmt_rs::formatter::DEFAULT_PATTERN_CACHE_CAPACITY;
+use numfmt_rs::{
+    FormatterOptions, PatternCacheStats, clear_pattern_cache, format, format_with_options,
+    pattern_cache_stats, set_pattern_cache_capacity,
+};
+
+// The cache is process-wide, so everything runs in one test to keep the
+// counters free of interference.
+#[test]
+fn cache_is_bounded_and_observable() {
+    clear_pattern_cache();
+    let stats = pattern_cache_stats();
+    assert_eq!((stats.hits, stats.misses, stats.size), (0, 0, 0));
+    assert_eq!(stats.capacity, DEFAULT_PATTERN_CACHE_CAPACITY);
+
+    format("0.00", 1.0).unwrap();
+    format("0.00", 2.0).unwrap();
+    format("#,##0", 3.0).unwrap();
+    let stats = pattern_cache_stats();
+    assert_eq!((stats.hits, stats.misses, stats.size), (1, 2, 2));
+
+    // Least recently used entries go first.
+    set_pattern_cache_capacity(2);
+    format("0.00", 1.0).unwrap();
+    format("0%", 1.0).unwrap();
+    let stats = pattern_cache_stats();
+    assert_eq!((stats.size, stats.evictions), (2, 1));
+    format("0.00", 1.0).unwrap();
+    format("#,##0", 1.0).unwrap();
+    let stats = pattern_cache_stats();
+    assert_eq!((stats.hits, stats.misses), (3, 4));
+
+    set_pattern_cache_capacity(1);
+    assert_eq!(pattern_cache_stats().size, 1);
+
+    // Invalid patterns are cached and keep reporting errors.
+    clear_pattern_cache();
+    assert!(format("0;0;0;0;0", 1.0).is_err());
+    assert!(format("0;0;0;0;0", 1.0).is_err());
+    assert_eq!(pattern_cache_stats().hits, 1);
+
+    // Opting out leaves the cache untouched.
+    clear_pattern_cache();
+    let options = FormatterOptions::default().with_cache_patterns(false);
+    assert_eq!(
+        format_with_options("0.0", 1.25, options.clone()).unwrap(),
+        "1.3"
+    );
+    assert!(format_with_options("0;0;0;0;0", 1.0, options).is_err());
+    assert_eq!(
+        pattern_cache_stats(),
+        PatternCacheStats {
+            capacity: 1,
+            ..Default::default()
+        }
+    );
+
+    set_pattern_cache_capacity(0);
+    format("0.0", 1.0).unwrap();
+    assert_eq!(pattern_cache_stats().size, 0);
+
+    set_pattern_cache_capacity(DEFAULT_PATTERN_CACHE_CAPACITY);
+}
@@ -0,0 +1,65 @@
+use numfmt_rs::{NegativeStyle, parse_pattern};
+
+fn edit(
+    pattern: &str,
+    op: impl Fn(&numfmt_rs::parser::Pattern) -> numfmt_rs::parser::Pattern,
+) -> String {
+    op(&parse_pattern(pattern).unwrap()).to_string()
+}
+
+#[test]
+fn changes_decimals_in_every_numeric_section() {
+    let more = |p: &str| edit(p, |p| p.increase_decimals().unwrap());
+    let fewer = |p: &str| edit(p, |p| p.decrease_decimals().unwrap());
+
+    assert_eq!(more("0"), "0.0");
+    assert_eq!(more("#,##0.00"), "#,##0.000");
+    assert_eq!(more("0%"), "0.0%");
+    assert_eq!(more("0.00E+00"), "0.000E+00");
+    assert_eq!(more("0."), "0.0");
+    assert_eq!(more("0.E+00"), "0.0E+00");
+    assert_eq!(more("0,\"K\""), "0.0,\\K");
+    assert_eq!(
+        more("[Blue]$#,##0 \"CR\";[Red]($#,##0);\"zero\";@"),
+        "[Blue]$#,##0.0 \"CR\";[Red]($#,##0.0);\"zero\";@"
+    );
+    assert_eq!(more("[>=100]0;0"), "[>=100]0.0;0.0");
+    assert_eq!(more("yyyy-mm-dd"), "yyyy-mm-dd");
+    assert_eq!(more("# ?/?"), "# ?/?");
+
+    assert_eq!(fewer("0.0"), "0");
+    assert_eq!(fewer("#,##0.00;(#,##0.00)"), "#,##0.0;(#,##0.0)");
+    assert_eq!(fewer("0"), "0");
+}
+
+#[test]
+fn toggles_grouping() {
+    let toggle = |p: &str| edit(p, |p| p.toggle_grouping().unwrap());
+    assert_eq!(toggle("0.00"), "#,##0.00");
+    assert_eq!(toggle("#,##0.00;[Red]-#,##0.00"), "0.00;[Red]-0.00");
+    assert_eq!(toggle("[$-407]0\" €\""), "[$-407]#,##0\" €\"");
+}
+
+#[test]
+fn sets_the_negative_style() {
+    let style = |p: &str, s: NegativeStyle| edit(p, |p| p.with_negative_style(s).unwrap());
+    assert_eq!(
+        style("#,##0.00", NegativeStyle::Parentheses),
+        "#,##0.00_);(#,##0.00)"
+    );
+    assert_eq!(
+        style("[Blue]#,##0.00", NegativeStyle::RedParentheses),
+        "[Blue]#,##0.00_);[Red](#,##0.00)"
+    );
+    assert_eq!(style("0.0;(0.0)", NegativeStyle::Red), "0.0;[Red]0.0");
+    assert_eq!(
+        style("$#,##0_);($#,##0)", NegativeStyle::RedMinus),
+        "$#,##0;[Red]-$#,##0"
+    );
+    assert_eq!(style("0.00_);(0.00)", NegativeStyle::Minus), "0.00");
+    assert_eq!(
+        style("0;(0);\"zero\"", NegativeStyle::Minus),
+        "0;-0;\"zero\""
+    );
+    assert_eq!(style("[<0]0;0", NegativeStyle::Red), "[<0]0;0");
+}
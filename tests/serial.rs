@@ -0,0 +1,158 @@
+use numfmt_rs::constants::{EPOCH_1317, EPOCH_1900, EPOCH_1904, MAX_L_DATE, MAX_S_DATE};
+use numfmt_rs::formatter::serial::{SerialError, check_serial, weekday};
+use numfmt_rs::{DateValue, FormatterOptions, format, format_with_options};
+
+fn ymd(year: i32, month: u8, day: u8) -> DateValue {
+    DateValue::new(year).with_month(month).with_day(day)
+}
+
+fn show(date: &DateValue) -> String {
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:03}",
+        date.year,
+        date.month.unwrap(),
+        date.day.unwrap(),
+        date.hour.unwrap(),
+        date.minute.unwrap(),
+        date.second.unwrap(),
+        date.millisecond.unwrap_or(0)
+    )
+}
+
+#[test]
+fn converts_dates_around_the_phantom_leap_day() {
+    let cases = [
+        ((1899, 12, 29), -1.0, -1.0),
+        ((1899, 12, 31), 0.0, 1.0),
+        ((1900, 1, 1), 1.0, 2.0),
+        ((1900, 2, 28), 59.0, 60.0),
+        ((1900, 3, 1), 61.0, 61.0),
+        ((2024, 3, 5), 45_356.0, 45_356.0),
+    ];
+    for ((y, m, d), lotus, real) in cases {
+        assert_eq!(ymd(y, m, d).to_serial(EPOCH_1900, true), Ok(lotus));
+        assert_eq!(ymd(y, m, d).to_serial(EPOCH_1900, false), Ok(real));
+    }
+    assert_eq!(ymd(1900, 2, 29).to_serial(EPOCH_1900, true), Ok(60.0));
+    assert_eq!(
+        ymd(1900, 2, 29).to_serial(EPOCH_1900, false),
+        Err(SerialError::InvalidDate)
+    );
+    assert_eq!(
+        ymd(1899, 12, 30).to_serial(EPOCH_1900, true),
+        Err(SerialError::InvalidDate)
+    );
+    assert_eq!(
+        ymd(2023, 2, 29).to_serial(EPOCH_1900, true),
+        Err(SerialError::InvalidDate)
+    );
+}
+
+#[test]
+fn converts_in_the_1904_system() {
+    let date = ymd(2024, 3, 5).with_time(18, 0, 0);
+    assert_eq!(date.to_serial(EPOCH_1904, true), Ok(43_894.75));
+    assert_eq!(
+        ymd(1903, 12, 31).to_serial(EPOCH_1904, true),
+        Err(SerialError::InvalidDate)
+    );
+    assert_eq!(
+        DateValue::from_serial(43_894.75, EPOCH_1904, true),
+        Ok(date)
+    );
+    assert_eq!(
+        DateValue::from_serial(-1.0, EPOCH_1904, true),
+        Err(SerialError::OutOfRange(-1.0))
+    );
+}
+
+#[test]
+fn rounds_times_up_into_the_next_day() {
+    let serial = 45_355.999_999_999_9;
+    let date = DateValue::from_serial(serial, EPOCH_1900, true).unwrap();
+    assert_eq!(show(&date), "2024-03-05 00:00:00.000");
+    assert_eq!(
+        format("yyyy-mm-dd hh:mm:ss", serial).unwrap(),
+        "2024-03-05 00:00:00"
+    );
+    assert_eq!(
+        format("yyyy-mm-dd hh:mm:ss", 45_355.999_999).unwrap(),
+        "2024-03-05 00:00:00"
+    );
+    assert_eq!(
+        format("yyyy-mm-dd hh:mm:ss.000", 45_355.999_999).unwrap(),
+        "2024-03-04 23:59:59.914"
+    );
+}
+
+#[test]
+fn reads_serials_as_the_formatter_renders_them() {
+    let options = FormatterOptions::default();
+    let serials = [
+        -700.25,
+        -1.5,
+        0.0,
+        1.0,
+        59.999,
+        60.0,
+        60.5,
+        61.0,
+        1_000.123,
+        45_355.999_999_999_9,
+        45_356.75,
+        2_958_465.5,
+    ];
+    for serial in serials {
+        for leap in [true, false] {
+            let options = FormatterOptions {
+                leap_1900: leap,
+                ..options.clone()
+            };
+            let date = DateValue::from_serial(serial, EPOCH_1900, leap).unwrap();
+            assert_eq!(
+                format_with_options("yyyy-mm-dd hh:mm:ss.000", serial, options).unwrap(),
+                show(&date),
+                "serial {serial}, leap {leap}"
+            );
+        }
+        let names = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+        let day = weekday(serial, EPOCH_1900).unwrap();
+        assert_eq!(format("ddd", serial).unwrap(), names[day as usize]);
+    }
+}
+
+#[test]
+fn round_trips_serials() {
+    for serial in [-5_000.5, 1.0, 59.0, 60.0, 61.25, 45_356.123] {
+        let date = DateValue::from_serial(serial, EPOCH_1900, true).unwrap();
+        let back = date.to_serial(EPOCH_1900, true).unwrap();
+        assert!((back - serial).abs() < 1e-8, "{serial} came back as {back}");
+    }
+    let date = DateValue::from_serial(45_356.5, EPOCH_1317, true).unwrap();
+    assert_eq!((date.year, date.month, date.day), (1445, Some(8), Some(25)));
+    assert_eq!(date.to_serial(EPOCH_1317, true), Ok(45_356.5));
+}
+
+#[test]
+fn checks_ranges_and_systems() {
+    assert_eq!(
+        DateValue::from_serial(MAX_L_DATE, EPOCH_1900, true),
+        Err(SerialError::OutOfRange(MAX_L_DATE))
+    );
+    assert!(matches!(
+        DateValue::from_serial(f64::NAN, EPOCH_1900, true),
+        Err(SerialError::OutOfRange(_))
+    ));
+    assert!(check_serial(MAX_S_DATE - 1.0, EPOCH_1900, false).is_ok());
+    assert!(check_serial(MAX_S_DATE, EPOCH_1900, false).is_err());
+    assert!(check_serial(-1.0, EPOCH_1900, false).is_err());
+    assert_eq!(
+        ymd(2024, 1, 1).to_serial(42, true),
+        Err(SerialError::UnknownSystem(42))
+    );
+    assert_eq!(
+        DateValue::from_serial(60.0, EPOCH_1317, true),
+        Err(SerialError::InvalidDate)
+    );
+    assert_eq!(ymd(2024, 3, 5).weekday(), 2);
+}
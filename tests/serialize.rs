@@ -0,0 +1,63 @@
+use numfmt_rs::{FormatterOptions, format_with_options, parse_pattern};
+use serde_json::Value;
+
+fn canonical(pattern: &str) -> String {
+    parse_pattern(pattern).unwrap().to_string()
+}
+
+#[test]
+fn equivalent_spellings_share_a_code() {
+    assert_eq!(canonical("#,##0.00"), "#,##0.00");
+    assert_eq!(canonical("#,###0.00"), "#,##0.00");
+    assert_eq!(canonical("##0"), "0");
+    assert_eq!(canonical("0 \"CR\""), canonical("0 \\C\\R"));
+    assert_eq!(canonical("0 \"CR\""), "0 \"CR\"");
+    assert_eq!(canonical("\\$0"), "$0");
+    assert_eq!(canonical("[red]0;[BLUE](0)"), "[Red]0;[Blue](0)");
+    assert_eq!(canonical("0.0,,"), "0.0,,");
+    assert_eq!(canonical("0.0%"), "0.0%");
+}
+
+#[test]
+fn leaves_out_generated_sections_and_conditions() {
+    assert_eq!(canonical("0"), "0");
+    assert_eq!(canonical("@"), "@");
+    assert_eq!(canonical("0;@"), "0;@");
+    assert_eq!(canonical("[>=100]0;0"), "[>=100]0;0");
+    assert_eq!(canonical("[<0]\"neg\"0"), "[<0]\"neg\"0");
+    assert_eq!(
+        canonical("yyyy-mm-dd hh:mm:ss.000"),
+        "yyyy-mm-dd hh:mm:ss.000"
+    );
+    assert_eq!(canonical("[h]:mm"), "[h]:mm");
+}
+
+#[test]
+fn canonical_codes_format_like_the_original() {
+    let dir = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/numfmt/generated"
+    );
+    let mut checked = 0;
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let content = std::fs::read_to_string(entry.unwrap().path()).unwrap();
+        let cases: Vec<Value> = serde_json::from_str(&content).unwrap();
+        for case in cases {
+            let (Some(pattern), Some(value)) = (case["pattern"].as_str(), case["value"].as_f64())
+            else {
+                continue;
+            };
+            let Ok(parsed) = parse_pattern(pattern) else {
+                continue;
+            };
+            let code = parsed.to_string();
+            let options = FormatterOptions::default();
+            let expected = format_with_options(pattern, value, options.clone()).ok();
+            let actual = format_with_options(&code, value, options).ok();
+            assert_eq!(actual, expected, "{pattern:?} was written as {code:?}");
+            assert_eq!(canonical(&code), code, "{pattern:?} is not stable");
+            checked += 1;
+        }
+    }
+    assert!(checked > 1000);
+}
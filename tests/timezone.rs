@@ -0,0 +1,234 @@
+#![cfg(feature = "tzdb")]
+
+use numfmt_rs::{DateValue, FormatterError, FormatterOptions, TimeZone, format_with_options};
+
+const STAMP: &str = "yyyy-mm-dd hh:mm";
+
+fn at(y: i32, m: u8, d: u8, h: u8, min: u8) -> DateValue {
+    DateValue::new(y)
+        .with_month(m)
+        .with_day(d)
+        .with_time(h, min, 0)
+}
+
+fn render_in(zone: &str, date: DateValue) -> String {
+    let options = FormatterOptions::default().with_time_zone(TimeZone::parse(zone).unwrap());
+    format_with_options(STAMP, date, options).unwrap()
+}
+
+#[test]
+fn parses_zone_names_and_offsets() {
+    assert_eq!(TimeZone::parse("Z"), Some(TimeZone::Offset(0)));
+    assert_eq!(TimeZone::parse("UTC"), Some(TimeZone::Offset(0)));
+    assert_eq!(TimeZone::parse("+05:30"), Some(TimeZone::Offset(19_800)));
+    assert_eq!(TimeZone::parse("UTC-0800"), Some(TimeZone::Offset(-28_800)));
+    assert_eq!(
+        TimeZone::parse("europe/berlin"),
+        Some(TimeZone::Named("europe/berlin".to_string()))
+    );
+    assert_eq!(TimeZone::parse("Mars/Olympus_Mons"), None);
+    assert_eq!(TimeZone::parse("+25:00"), None);
+}
+
+#[test]
+fn keeps_the_wall_clock_without_a_render_zone() {
+    let date = at(2024, 7, 1, 9, 30).with_offset(-4 * 3600);
+    assert_eq!(
+        format_with_options(STAMP, date, FormatterOptions::default()).unwrap(),
+        "2024-07-01 09:30"
+    );
+    // Dates without a zone are floating and never move.
+    assert_eq!(
+        render_in("Asia/Tokyo", at(2024, 7, 1, 9, 30)),
+        "2024-07-01 09:30"
+    );
+}
+
+#[test]
+fn converts_between_zones() {
+    let utc = |date: DateValue| date.with_zone(TimeZone::utc());
+    assert_eq!(
+        render_in("+05:30", utc(at(2024, 1, 1, 20, 0))),
+        "2024-01-02 01:30"
+    );
+    assert_eq!(
+        render_in("America/New_York", utc(at(2024, 1, 15, 12, 0))),
+        "2024-01-15 07:00"
+    );
+    assert_eq!(
+        render_in("America/New_York", utc(at(2024, 7, 15, 12, 0))),
+        "2024-07-15 08:00"
+    );
+    assert_eq!(
+        render_in("Europe/Berlin", utc(at(2024, 7, 15, 12, 0))),
+        "2024-07-15 14:00"
+    );
+    assert_eq!(
+        render_in("Australia/Sydney", utc(at(2024, 1, 15, 12, 0))),
+        "2024-01-15 23:00"
+    );
+    assert_eq!(
+        render_in("Australia/Sydney", utc(at(2024, 7, 15, 12, 0))),
+        "2024-07-15 22:00"
+    );
+
+    let berlin = at(2024, 12, 24, 18, 0).with_zone(TimeZone::parse("Europe/Berlin").unwrap());
+    assert_eq!(render_in("America/Los_Angeles", berlin), "2024-12-24 09:00");
+}
+
+#[test]
+fn follows_daylight_saving_transitions() {
+    let utc = |date: DateValue| date.with_zone(TimeZone::utc());
+    // Europe switches at 01:00 UTC on the last Sunday of March and October.
+    assert_eq!(
+        render_in("Europe/London", utc(at(2024, 3, 31, 0, 59))),
+        "2024-03-31 00:59"
+    );
+    assert_eq!(
+        render_in("Europe/London", utc(at(2024, 3, 31, 1, 0))),
+        "2024-03-31 02:00"
+    );
+    assert_eq!(
+        render_in("Europe/London", utc(at(2024, 10, 27, 0, 59))),
+        "2024-10-27 01:59"
+    );
+    assert_eq!(
+        render_in("Europe/London", utc(at(2024, 10, 27, 1, 0))),
+        "2024-10-27 01:00"
+    );
+    // The US switches at 02:00 local time.
+    assert_eq!(
+        render_in("America/Chicago", utc(at(2024, 3, 10, 7, 59))),
+        "2024-03-10 01:59"
+    );
+    assert_eq!(
+        render_in("America/Chicago", utc(at(2024, 3, 10, 8, 0))),
+        "2024-03-10 03:00"
+    );
+    assert_eq!(
+        render_in("America/Chicago", utc(at(2024, 11, 3, 6, 59))),
+        "2024-11-03 01:59"
+    );
+    assert_eq!(
+        render_in("America/Chicago", utc(at(2024, 11, 3, 7, 0))),
+        "2024-11-03 01:00"
+    );
+
+    // A skipped wall time reads with the offset from before the jump.
+    let skipped = at(2024, 3, 10, 2, 30).with_zone(TimeZone::parse("America/Chicago").unwrap());
+    assert_eq!(render_in("UTC", skipped), "2024-03-10 08:30");
+    // A repeated one resolves to its first occurrence.
+    let repeated = at(2024, 11, 3, 1, 30).with_zone(TimeZone::parse("America/Chicago").unwrap());
+    assert_eq!(render_in("UTC", repeated), "2024-11-03 06:30");
+}
+
+#[test]
+fn ignore_timezone_keeps_the_wall_clock() {
+    let date = at(2024, 7, 1, 9, 30).with_zone(TimeZone::utc());
+    let options = FormatterOptions {
+        ignore_timezone: true,
+        ..FormatterOptions::default().with_time_zone(TimeZone::parse("Asia/Tokyo").unwrap())
+    };
+    assert_eq!(
+        format_with_options(STAMP, date, options).unwrap(),
+        "2024-07-01 09:30"
+    );
+}
+
+#[test]
+fn follows_historical_rule_changes() {
+    let utc = |date: DateValue| date.with_zone(TimeZone::utc());
+    let cases = [
+        // The US started daylight saving on the first Sunday of April and
+        // ended it on the last Sunday of October until 2007.
+        (
+            "America/New_York",
+            at(2006, 4, 1, 12, 0),
+            "2006-04-01 07:00",
+        ),
+        (
+            "America/New_York",
+            at(2006, 10, 30, 12, 0),
+            "2006-10-30 07:00",
+        ),
+        (
+            "America/New_York",
+            at(2007, 4, 1, 12, 0),
+            "2007-04-01 08:00",
+        ),
+        // Mexico dropped daylight saving in 2023.
+        (
+            "America/Mexico_City",
+            at(2022, 7, 15, 12, 0),
+            "2022-07-15 07:00",
+        ),
+        (
+            "America/Mexico_City",
+            at(2023, 7, 15, 12, 0),
+            "2023-07-15 06:00",
+        ),
+        // Brazil dropped it in 2019.
+        (
+            "America/Sao_Paulo",
+            at(2018, 1, 15, 12, 0),
+            "2018-01-15 10:00",
+        ),
+        (
+            "America/Sao_Paulo",
+            at(2024, 1, 15, 12, 0),
+            "2024-01-15 09:00",
+        ),
+        // Turkey stayed on +03:00 from late 2016.
+        (
+            "Europe/Istanbul",
+            at(2015, 1, 15, 12, 0),
+            "2015-01-15 14:00",
+        ),
+        (
+            "Europe/Istanbul",
+            at(2024, 1, 15, 12, 0),
+            "2024-01-15 15:00",
+        ),
+        // Moscow kept +04:00 between 2011 and 2014.
+        ("Europe/Moscow", at(2012, 1, 15, 12, 0), "2012-01-15 16:00"),
+        ("Europe/Moscow", at(2024, 1, 15, 12, 0), "2024-01-15 15:00"),
+        // Iran dropped daylight saving in 2022.
+        ("Asia/Tehran", at(2021, 7, 15, 12, 0), "2021-07-15 16:30"),
+        ("Asia/Tehran", at(2024, 7, 15, 12, 0), "2024-07-15 15:30"),
+        ("Asia/Jerusalem", at(2024, 1, 15, 12, 0), "2024-01-15 14:00"),
+        ("Asia/Jerusalem", at(2024, 7, 15, 12, 0), "2024-07-15 15:00"),
+        // Chile's summer falls in January.
+        (
+            "America/Santiago",
+            at(2024, 1, 15, 12, 0),
+            "2024-01-15 09:00",
+        ),
+        (
+            "America/Santiago",
+            at(2024, 7, 15, 12, 0),
+            "2024-07-15 08:00",
+        ),
+        // Egypt brought daylight saving back in 2023.
+        ("Africa/Cairo", at(2022, 7, 15, 12, 0), "2022-07-15 14:00"),
+        ("Africa/Cairo", at(2024, 7, 15, 12, 0), "2024-07-15 15:00"),
+    ];
+    for (zone, date, expected) in cases {
+        assert_eq!(render_in(zone, utc(date)), expected, "{zone}");
+    }
+}
+
+#[test]
+fn unknown_zones_render_as_invalid() {
+    let date = at(2024, 7, 1, 9, 30).with_zone(TimeZone::Named("Nowhere/Special".to_string()));
+    assert_eq!(render_in("UTC", date.clone()), "######");
+
+    let options = FormatterOptions {
+        date_error_throws: true,
+        invalid: "?".to_string(),
+        ..FormatterOptions::default().with_time_zone(TimeZone::utc())
+    };
+    assert!(matches!(
+        format_with_options(STAMP, date, options),
+        Err(FormatterError::UnknownTimeZone(id)) if id == "Nowhere/Special"
+    ));
+}